@@ -0,0 +1,59 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use rt_ref::Cell;
+
+const ITERATIONS: u32 = 1_000;
+
+fn borrow_uncontended(c: &mut Criterion) {
+    let mut group = c.benchmark_group("borrow_uncontended");
+
+    group.bench_function("borrow", |b| {
+        let cell = Cell::new(1u32);
+        b.iter(|| {
+            let mut sum = 0u32;
+            for _ in 0..ITERATIONS {
+                sum = sum.wrapping_add(*black_box(&cell).borrow());
+            }
+            sum
+        })
+    });
+
+    group.bench_function("try_borrow", |b| {
+        let cell = Cell::new(1u32);
+        b.iter(|| {
+            let mut sum = 0u32;
+            for _ in 0..ITERATIONS {
+                if let Ok(n) = black_box(&cell).try_borrow() {
+                    sum = sum.wrapping_add(*n);
+                }
+            }
+            sum
+        })
+    });
+
+    group.bench_function("borrow_mut", |b| {
+        let cell = Cell::new(1u32);
+        b.iter(|| {
+            for _ in 0..ITERATIONS {
+                *black_box(&cell).borrow_mut() += 1;
+            }
+        })
+    });
+
+    group.bench_function("try_borrow_mut", |b| {
+        let cell = Cell::new(1u32);
+        b.iter(|| {
+            for _ in 0..ITERATIONS {
+                if let Ok(mut n) = black_box(&cell).try_borrow_mut() {
+                    *n += 1;
+                }
+            }
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, borrow_uncontended);
+criterion_main!(benches);
@@ -0,0 +1,40 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use rt_ref::Cell;
+
+const ITERATIONS: u32 = 1_000;
+
+fn borrow_in_loop(c: &mut Criterion) {
+    let mut group = c.benchmark_group("borrow_in_loop");
+
+    group.bench_function("borrow", |b| {
+        let cell = Cell::new(1u32);
+        b.iter(|| {
+            let mut sum = 0u32;
+            for _ in 0..ITERATIONS {
+                sum = sum.wrapping_add(*black_box(&cell).borrow());
+            }
+            sum
+        })
+    });
+
+    group.bench_function("reuse", |b| {
+        let cell = Cell::new(1u32);
+        b.iter(|| {
+            let mut sum = 0u32;
+            let mut token = cell.borrow().park();
+            for _ in 0..ITERATIONS {
+                let cell_ref = black_box(&cell).reuse(token);
+                sum = sum.wrapping_add(*cell_ref);
+                token = cell_ref.park();
+            }
+            sum
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, borrow_in_loop);
+criterion_main!(benches);
@@ -0,0 +1,128 @@
+//! Interprets the input as a sequence of borrow operations against a `Cell`,
+//! and checks the borrow flag against a model of the live guards after each
+//! step.
+//!
+//! Each byte selects an operation. `DropNth` consumes the following byte as
+//! the index of the guard to drop.
+//!
+//! `libfuzzer-sys` aborts on panic, so conflicts are exercised through the
+//! `try_*` methods: a conflict must be reported exactly when `borrow` /
+//! `borrow_mut` would panic.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rt_ref::{Cell, CellRef, CellRefMut};
+
+/// Largest value of the flag for immutable borrows.
+const REF_LIMIT_MAX: usize = isize::MAX as usize;
+
+#[derive(Clone, Copy, Debug)]
+enum Op {
+    Borrow,
+    BorrowMut,
+    Clone,
+    DropNth,
+    Map,
+    MapMut,
+}
+
+impl Op {
+    fn from_byte(byte: u8) -> Self {
+        match byte % 6 {
+            0 => Op::Borrow,
+            1 => Op::BorrowMut,
+            2 => Op::Clone,
+            3 => Op::DropNth,
+            4 => Op::Map,
+            _ => Op::MapMut,
+        }
+    }
+}
+
+/// Live guards, which model the expected value of the borrow flag.
+#[derive(Default)]
+struct Guards<'a> {
+    readers: Vec<CellRef<'a, u32>>,
+    writer: Option<CellRefMut<'a, u32>>,
+}
+
+impl Guards<'_> {
+    fn expected_flag(&self) -> usize {
+        if self.writer.is_some() {
+            usize::MAX
+        } else {
+            self.readers.len()
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.readers.len() + usize::from(self.writer.is_some())
+    }
+}
+
+fuzz_target!(|data: &[u8]| {
+    let cell = Cell::new(0u32);
+    let mut writes = 0u32;
+
+    {
+        let mut guards = Guards::default();
+        let mut bytes = data.iter().copied();
+
+        while let Some(byte) = bytes.next() {
+            match Op::from_byte(byte) {
+                Op::Borrow => {
+                    let result = cell.try_borrow();
+                    assert_eq!(guards.writer.is_some(), result.is_err());
+                    if let Ok(r) = result {
+                        assert_eq!(writes, *r);
+                        guards.readers.push(r);
+                    }
+                }
+                Op::BorrowMut => {
+                    let result = cell.try_borrow_mut();
+                    assert_eq!(guards.len() != 0, result.is_err());
+                    if let Ok(mut r) = result {
+                        *r += 1;
+                        writes += 1;
+                        guards.writer = Some(r);
+                    }
+                }
+                Op::Clone => {
+                    if let Some(r) = guards.readers.first() {
+                        let clone = r.try_clone().expect("Expected clone to succeed.");
+                        guards.readers.push(clone);
+                    }
+                }
+                Op::DropNth => {
+                    let len = guards.len();
+                    if len != 0 {
+                        let n = usize::from(bytes.next().unwrap_or(0)) % len;
+                        if n < guards.readers.len() {
+                            drop(guards.readers.swap_remove(n));
+                        } else {
+                            drop(guards.writer.take());
+                        }
+                    }
+                }
+                Op::Map => {
+                    if let Some(r) = guards.readers.pop() {
+                        guards.readers.push(r.map(|v| v));
+                    }
+                }
+                Op::MapMut => {
+                    if let Some(r) = guards.writer.take() {
+                        guards.writer = Some(r.map(|v| v));
+                    }
+                }
+            }
+
+            let flag = cell.flag_raw();
+            assert!(flag <= REF_LIMIT_MAX || flag == usize::MAX);
+            assert_eq!(guards.expected_flag(), flag);
+        }
+    }
+
+    assert_eq!(0, cell.flag_raw());
+    assert_eq!(writes, *cell.borrow());
+});
@@ -0,0 +1,132 @@
+use std::ops::Deref;
+
+use rkyv::{
+    bytecheck::CheckBytes,
+    rancor::Fallible,
+    traits::{NoUndef, Portable},
+    Archive, Deserialize, Place, Serialize,
+};
+
+use crate::Cell;
+
+/// An archived [`Cell`].
+///
+/// Only the inner value is archived -- the borrow flag is not part of the
+/// archived form, and is reset to `0` when deserialized.
+///
+/// Access the archived value via `std::ops::Deref` (e.g. `*val`)
+#[repr(transparent)]
+pub struct ArchivedCell<T>
+where
+    T: Archive,
+{
+    inner: T::Archived,
+}
+
+impl<T> Deref for ArchivedCell<T>
+where
+    T: Archive,
+{
+    type Target = T::Archived;
+
+    fn deref(&self) -> &T::Archived {
+        &self.inner
+    }
+}
+
+// `ArchivedCell` is a transparent wrapper around `T::Archived`, which is
+// `Portable`.
+unsafe impl<T> Portable for ArchivedCell<T> where T: Archive {}
+
+unsafe impl<T> NoUndef for ArchivedCell<T>
+where
+    T: Archive,
+    T::Archived: NoUndef,
+{
+}
+
+unsafe impl<T, C> CheckBytes<C> for ArchivedCell<T>
+where
+    T: Archive,
+    T::Archived: CheckBytes<C>,
+    C: Fallible + ?Sized,
+{
+    unsafe fn check_bytes(value: *const Self, context: &mut C) -> Result<(), C::Error> {
+        T::Archived::check_bytes(value.cast(), context)
+    }
+}
+
+impl<T> Archive for Cell<T>
+where
+    T: Archive,
+{
+    type Archived = ArchivedCell<T>;
+    type Resolver = T::Resolver;
+
+    /// # Panics
+    ///
+    /// Panics if the value is currently borrowed mutably.
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        let out = unsafe { out.cast_unchecked::<T::Archived>() };
+        self.borrow().resolve(resolver, out);
+    }
+}
+
+impl<T, S> Serialize<S> for Cell<T>
+where
+    T: Serialize<S>,
+    S: Fallible + ?Sized,
+{
+    /// # Panics
+    ///
+    /// Panics if the value is currently borrowed mutably.
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        self.borrow().serialize(serializer)
+    }
+}
+
+impl<T, D> Deserialize<Cell<T>, D> for ArchivedCell<T>
+where
+    T: Archive,
+    T::Archived: Deserialize<T, D>,
+    D: Fallible + ?Sized,
+{
+    fn deserialize(&self, deserializer: &mut D) -> Result<Cell<T>, D::Error> {
+        self.inner.deserialize(deserializer).map(Cell::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rkyv::rancor::Error;
+
+    use crate::Cell;
+
+    use super::ArchivedCell;
+
+    #[test]
+    fn round_trip_resets_flag() -> Result<(), Error> {
+        let cell = Cell::new(vec![1u32, 2, 3]);
+        let _borrow = cell.borrow();
+        assert_eq!(1, cell.flag_raw());
+
+        let bytes = rkyv::to_bytes::<Error>(&cell)?;
+        let archived = rkyv::access::<ArchivedCell<Vec<u32>>, Error>(&bytes)?;
+        assert_eq!(&[1, 2, 3], archived.as_slice());
+
+        let deserialized = rkyv::deserialize::<Cell<Vec<u32>>, Error>(archived)?;
+        assert_eq!(0, deserialized.flag_raw());
+        assert_eq!(vec![1, 2, 3], *deserialized.borrow());
+
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "already borrowed mutably")]
+    fn serialize_panics_when_borrowed_mutably() {
+        let cell = Cell::new(vec![1u32, 2, 3]);
+        let _borrow = cell.borrow_mut();
+
+        let _ = rkyv::to_bytes::<Error>(&cell);
+    }
+}
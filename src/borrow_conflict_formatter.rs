@@ -0,0 +1,49 @@
+use std::sync::RwLock;
+
+/// Formats the panic message when a borrow conflicts.
+///
+/// The arguments are the name of the borrowed type, and the default message.
+type Formatter = fn(&str, &str) -> String;
+
+static FORMATTER: RwLock<Option<Formatter>> = RwLock::new(None);
+
+/// Sets the function used to build the panic message when a `Cell` is
+/// borrowed while it is already borrowed, e.g. to include an application
+/// specific resource ID.
+///
+/// `f` receives the name of the borrowed type, and the default message. This
+/// applies to all `Cell`s, and replaces any previously set formatter.
+///
+/// # Examples
+///
+/// ```rust,should_panic
+/// use rt_ref::Cell;
+///
+/// rt_ref::set_borrow_conflict_formatter(|type_name, message| {
+///     format!("[resource: {type_name}] {message}")
+/// });
+///
+/// let cell = Cell::new(1);
+/// let _a = cell.borrow_mut();
+/// let _b = cell.borrow(); // panics with "[resource: i32] Expected to borrow .."
+/// ```
+pub fn set_borrow_conflict_formatter(f: fn(&str, &str) -> String) {
+    let mut formatter = FORMATTER
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    *formatter = Some(f);
+}
+
+/// Returns the panic message for a borrow conflict, using the formatter if
+/// one is set.
+pub(crate) fn format(type_name: &str, message: String) -> String {
+    let formatter = *FORMATTER
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    match formatter {
+        Some(f) => f(type_name, &message),
+        None => message,
+    }
+}
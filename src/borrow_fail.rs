@@ -1,13 +1,120 @@
+use crate::cell_ref::REF_LIMIT_MAX;
+
 /// Failures to borrow a value.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum BorrowFail {
     /// Value was not found in the map.
     ValueNotFound,
     /// Requested an immutable borrow, but value was already borrowed mutably.
     BorrowConflictImm,
-    /// Requested a mutable borrow, but value was already borrowed.
+    /// Requested an immutable borrow, but the number of immutable borrows had
+    /// reached the `Cell`'s flag limit.
     ///
-    /// This variant is return whether the value was previously borrowed
-    /// immutably or mutably.
-    BorrowConflictMut,
+    /// This holds the number of immutable borrows when the borrow was
+    /// attempted. See [`CellBuilder::flag_limit`].
+    ///
+    /// [`CellBuilder::flag_limit`]: crate::CellBuilder::flag_limit
+    ReadLimitReached(usize),
+    /// Requested a mutable borrow, but value was already borrowed immutably.
+    ///
+    /// This holds the number of immutable borrows when the borrow was
+    /// attempted, which may be stale by the time it is read.
+    ReadLocked(usize),
+    /// Requested a mutable borrow, but value was already borrowed mutably.
+    WriteLocked,
+}
+
+impl BorrowFail {
+    /// Returns the failure for an immutable borrow, given the borrow flag at
+    /// the time the borrow was attempted.
+    pub(crate) fn conflict_imm(flag: usize) -> Self {
+        if flag > REF_LIMIT_MAX {
+            BorrowFail::BorrowConflictImm
+        } else {
+            BorrowFail::ReadLimitReached(flag)
+        }
+    }
+
+    /// Returns the failure for a mutable borrow, given the borrow flag at the
+    /// time the borrow was attempted.
+    pub(crate) fn conflict_mut(flag: usize) -> Self {
+        if flag > REF_LIMIT_MAX {
+            BorrowFail::WriteLocked
+        } else {
+            BorrowFail::ReadLocked(flag)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use crate::{cell_ref::REF_LIMIT_MAX, RefOverflow};
+
+    use super::BorrowFail;
+
+    #[test]
+    fn btree_set_dedups_borrow_fails() {
+        let borrow_fails = [
+            BorrowFail::WriteLocked,
+            BorrowFail::ValueNotFound,
+            BorrowFail::ReadLocked(2),
+            BorrowFail::WriteLocked,
+            BorrowFail::BorrowConflictImm,
+            BorrowFail::ReadLocked(2),
+            BorrowFail::ValueNotFound,
+        ]
+        .into_iter()
+        .collect::<BTreeSet<_>>();
+
+        assert_eq!(
+            vec![
+                BorrowFail::ValueNotFound,
+                BorrowFail::BorrowConflictImm,
+                BorrowFail::ReadLocked(2),
+                BorrowFail::WriteLocked,
+            ],
+            borrow_fails.into_iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn conflict_imm_distinguishes_limit_and_writers() {
+        assert_eq!(BorrowFail::ReadLimitReached(2), BorrowFail::conflict_imm(2));
+        assert_eq!(
+            BorrowFail::ReadLimitReached(REF_LIMIT_MAX),
+            BorrowFail::conflict_imm(REF_LIMIT_MAX)
+        );
+        assert_eq!(
+            BorrowFail::BorrowConflictImm,
+            BorrowFail::conflict_imm(usize::MAX)
+        );
+    }
+
+    #[test]
+    fn conflict_mut_distinguishes_readers_and_writers() {
+        assert_eq!(BorrowFail::ReadLocked(1), BorrowFail::conflict_mut(1));
+        assert_eq!(
+            BorrowFail::ReadLocked(REF_LIMIT_MAX),
+            BorrowFail::conflict_mut(REF_LIMIT_MAX)
+        );
+        assert_eq!(
+            BorrowFail::WriteLocked,
+            BorrowFail::conflict_mut(usize::MAX)
+        );
+        assert_eq!(
+            BorrowFail::WriteLocked,
+            BorrowFail::conflict_mut(usize::MAX - 1)
+        );
+    }
+
+    #[test]
+    fn btree_set_dedups_ref_overflows() {
+        let ref_overflows = [RefOverflow::new::<u32>(1), RefOverflow::new::<u32>(1)]
+            .into_iter()
+            .collect::<BTreeSet<_>>();
+
+        assert_eq!(1, ref_overflows.len());
+    }
 }
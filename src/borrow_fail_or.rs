@@ -0,0 +1,20 @@
+use crate::BorrowFail;
+
+/// Failure to borrow a value, or an error from updating it.
+///
+/// Returned by [`Cell::try_update`].
+///
+/// [`Cell::try_update`]: crate::Cell::try_update
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BorrowFailOr<E> {
+    /// The value could not be borrowed.
+    Borrow(BorrowFail),
+    /// The value was borrowed, but the update returned an error.
+    Update(E),
+}
+
+impl<E> From<BorrowFail> for BorrowFailOr<E> {
+    fn from(borrow_fail: BorrowFail) -> Self {
+        BorrowFailOr::Borrow(borrow_fail)
+    }
+}
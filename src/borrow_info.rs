@@ -0,0 +1,15 @@
+use std::panic::Location;
+
+/// Information about an immutable borrow, captured when it is acquired.
+///
+/// Returned by [`Cell::try_borrow_info`].
+///
+/// [`Cell::try_borrow_info`]: crate::Cell::try_borrow_info
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BorrowInfo {
+    /// Number of immutable borrows, including this one, when the borrow was
+    /// acquired.
+    pub readers: usize,
+    /// Location of the code that acquired the borrow.
+    pub location: &'static Location<'static>,
+}
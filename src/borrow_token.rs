@@ -0,0 +1,111 @@
+use crate::flag::{release_reader, Flag};
+
+/// An immutable borrow of a `Cell`, parked so that it can be reused without
+/// re-acquiring it.
+///
+/// Returned by [`CellRef::park`]. Borrowing and releasing a `Cell` in a loop
+/// updates the borrow flag twice per iteration. Parking the `CellRef` instead
+/// keeps the borrow held, so that [`Cell::reuse`] returns a `CellRef` without
+/// updating the flag.
+///
+/// # Protocol
+///
+/// 1. Borrow the `Cell` immutably, and call [`CellRef::park`] when done with
+///    the `CellRef`, instead of dropping it.
+/// 2. Pass the token to [`Cell::reuse`] on the same `Cell` to get a `CellRef`
+///    to its value. This only checks that the token belongs to the `Cell`.
+/// 3. Repeat from 1, or drop the token to release the borrow.
+///
+/// While the token is held, the `Cell` is borrowed immutably, so it cannot be
+/// borrowed mutably.
+///
+/// [`CellRef::park`]: crate::CellRef::park
+/// [`Cell::reuse`]: crate::Cell::reuse
+#[derive(Debug)]
+pub struct BorrowToken<'a> {
+    /// Flag of the borrowed `Cell`, whose reader slot is released when this is
+    /// dropped.
+    pub(crate) flag: &'a Flag,
+}
+
+impl<'a> Drop for BorrowToken<'a> {
+    fn drop(&mut self) {
+        release_reader(self.flag);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::Ordering;
+
+    use crate::Cell;
+
+    #[test]
+    fn token_holds_borrow_until_dropped() {
+        let cell = Cell::new(1u32);
+
+        let token = cell.borrow().park();
+        assert_eq!(1, cell.flag.load(Ordering::SeqCst));
+        assert!(cell.try_borrow_mut().is_err());
+
+        drop(token);
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn reuse_does_not_update_flag() {
+        let cell = Cell::new(1u32);
+
+        let mut token = cell.borrow().park();
+        for _ in 0..3 {
+            let cell_ref = cell.reuse(token);
+            assert_eq!(1, *cell_ref);
+            assert_eq!(1, cell.flag.load(Ordering::SeqCst));
+
+            token = cell_ref.park();
+        }
+
+        drop(token);
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn reuse_keeps_borrows_from_other_cell_refs() {
+        let cell = Cell::new(1u32);
+
+        let cell_ref = cell.borrow();
+        let token = cell_ref.clone().park();
+        assert_eq!(2, cell.flag.load(Ordering::SeqCst));
+
+        let reused = cell.reuse(token);
+        drop(cell_ref);
+        assert_eq!(1, cell.flag.load(Ordering::SeqCst));
+
+        drop(reused);
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected `BorrowToken` to borrow from the given `Cell`.")]
+    fn reuse_panics_for_token_from_other_cell() {
+        let a = Cell::new(1u32);
+        let b = Cell::new(2u32);
+
+        let token = a.borrow().park();
+        let _b_ref = b.reuse(token);
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn reuse_does_not_record_borrows() {
+        let cell = Cell::new(1u32);
+
+        let mut token = cell.borrow().park();
+        for _ in 0..100 {
+            token = cell.reuse(token).park();
+        }
+        drop(token);
+
+        assert_eq!(1, cell.stats().borrows);
+    }
+}
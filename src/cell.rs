@@ -1,34 +1,93 @@
 use std::{
+    alloc::{self, Layout},
+    any::Any,
     cell::UnsafeCell,
-    sync::atomic::{AtomicUsize, Ordering},
+    fmt, mem, ptr,
+    sync::{atomic::Ordering, Arc, RwLock},
 };
 
-use crate::{cell_ref::REF_LIMIT_MAX, BorrowFail, CellRef, CellRefMut};
+#[cfg(feature = "track_borrows")]
+use std::panic::Location;
+#[cfg(feature = "timing")]
+use std::{
+    sync::atomic::AtomicU64,
+    time::{Duration, Instant},
+};
+
+#[cfg(feature = "tracing")]
+use tracing::span::EnteredSpan;
+
+#[cfg(feature = "track_borrows")]
+use crate::BorrowInfo;
+#[cfg(feature = "timing")]
+use crate::TimedRefMut;
+use crate::{
+    cell_ref::REF_LIMIT_MAX, flag::Flag, flag_limit::FlagLimit, BorrowFail, BorrowFailOr,
+    BorrowToken, CellRef, CellRefMut, FinalizingRefMut, HasLen, OwnedCellRef, OwnedCellRefMut,
+    ReentrantRef, Ref, RefMut,
+};
+#[cfg(feature = "metrics")]
+use crate::{cell_stats::CellStatsCounters, CellStats};
 
 macro_rules! borrow_panic {
-    ($borrow_wanted:expr, $borrow_existing:expr) => {{
-        panic!(
-            "Expected to borrow `{type_name}` {borrow_wanted}, but it was already borrowed{borrow_existing}.",
-            type_name = ::std::any::type_name::<T>(),
+    ($self:ident, $borrow_wanted:expr) => {
+        borrow_panic!($self, $borrow_wanted, $self.borrow_existing())
+    };
+    ($self:ident, $borrow_wanted:expr, $borrow_existing:expr) => {{
+        let borrow_existing = $borrow_existing;
+        let type_name = ::std::any::type_name::<T>();
+        let message = format!(
+            "Expected to borrow `{type_name}` {borrow_wanted}, but it was already borrowed{borrow_existing}",
             borrow_wanted = $borrow_wanted,
-            borrow_existing = $borrow_existing,
-        )
+        );
+
+        #[cfg(feature = "borrow_conflict_formatter")]
+        let message = crate::borrow_conflict_formatter::format(type_name, message);
+
+        panic!("{message}")
     }};
 }
 
 /// A custom cell container that is a `RefCell` with thread-safety.
-#[cfg_attr(not(feature = "unsafe_debug"), derive(Debug))]
-pub struct Cell<T> {
-    flag: AtomicUsize,
-    inner: UnsafeCell<T>,
+///
+/// Borrow rules are enforced for every `T`, including zero-sized types such as
+/// `()`, so a `Cell<()>` may be used as a lock-free presence marker.
+//
+// `repr(C)` fixes the field order and offsets, so that `Cell<[T; N]>` and
+// `Cell<[T]>` share the layout of the fields before `inner`. See
+// `Cell::from_boxed_slice`.
+#[repr(C)]
+pub struct Cell<T>
+where
+    T: ?Sized,
+{
+    /// Borrow count, and the maximum number of immutable borrows.
+    pub(crate) flag: Flag,
+    /// Number of borrows and conflicts.
+    #[cfg(feature = "metrics")]
+    stats: CellStatsCounters,
+    /// Longest duration of a `TimedRefMut` borrow, in nanoseconds.
+    #[cfg(feature = "timing")]
+    max_borrow_mut_nanos: AtomicU64,
+    /// This must be the last field, so that `T` may be unsized.
+    pub(crate) inner: UnsafeCell<T>,
 }
 
 impl<T> Cell<T> {
     /// Create a new cell, similar to `RefCell::new`
     pub fn new(inner: T) -> Self {
+        Self::new_with_limit(inner, FlagLimit::DEFAULT)
+    }
+
+    /// Create a new cell with the given limit on immutable borrows.
+    pub(crate) fn new_with_limit(inner: T, limit: FlagLimit) -> Self {
         Cell {
-            flag: AtomicUsize::new(0),
+            flag: Flag::with_limit(0, limit),
             inner: UnsafeCell::new(inner),
+            #[cfg(feature = "metrics")]
+            stats: CellStatsCounters::default(),
+            #[cfg(feature = "timing")]
+            max_borrow_mut_nanos: AtomicU64::new(0),
         }
     }
 
@@ -37,6 +96,346 @@ impl<T> Cell<T> {
         self.inner.into_inner()
     }
 
+    /// Consumes this cell and returns its value in a [`RwLock`].
+    ///
+    /// `Cell` and `RwLock` both allow many readers or one writer, but differ
+    /// in how conflicts and threads are handled:
+    ///
+    /// * A conflicting `Cell` borrow panics, or returns an error for the
+    ///   `try_*` methods. A conflicting `RwLock` lock blocks until the other
+    ///   guards are dropped.
+    /// * `Cell` borrows are never poisoned. `RwLock` is poisoned when a writer
+    ///   panics. A `Cell` is therefore not `RefUnwindSafe`, as a value that was
+    ///   partially mutated before a panic may be observed after it is caught.
+    /// * `Cell<T>` is `Sync` when `T: Send + Sync`, like `RwLock<T>`, except
+    ///   with the `"single_thread"` feature, where it is not `Sync`.
+    ///
+    /// Use `RwLock` when threads are expected to wait for each other, and
+    /// `Cell` when a conflict is a logic error. To convert in the other
+    /// direction, use `Cell::new(rw_lock.into_inner()?)`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::sync::RwLock;
+    ///
+    /// use rt_ref::Cell;
+    ///
+    /// let cell = Cell::new(1);
+    /// let rw_lock: RwLock<u32> = cell.into_rwlock();
+    ///
+    /// assert_eq!(1, *rw_lock.read().unwrap());
+    /// ```
+    pub fn into_rwlock(self) -> RwLock<T> {
+        RwLock::new(self.into_inner())
+    }
+
+    /// Consumes this cell and leaks `T`, returning a `'static` mutable
+    /// reference to it.
+    ///
+    /// This is intended for values that live for the remainder of the
+    /// program, such as global resources. The value is never dropped.
+    pub fn into_leaked(self) -> &'static mut T
+    where
+        T: 'static,
+    {
+        Box::leak(Box::new(self.into_inner()))
+    }
+
+    /// Get an immutable reference to the inner data as `dyn Any`.
+    ///
+    /// This is useful when storing `Cell`s of different types, and downcasting
+    /// the value later.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if there is a mutable reference to the data
+    /// already in use.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::any::Any;
+    ///
+    /// use rt_ref::{Cell, CellRef};
+    ///
+    /// let c = Cell::new(1u32);
+    ///
+    /// let value: CellRef<'_, dyn Any> = c.borrow_any();
+    /// assert_eq!(Some(&1u32), value.downcast_ref::<u32>());
+    /// ```
+    pub fn borrow_any(&self) -> CellRef<'_, dyn Any>
+    where
+        T: Any,
+    {
+        self.borrow().map(|value| value as &dyn Any)
+    }
+
+    /// Get a mutable reference to the inner data, which runs `on_release` on
+    /// the value before the borrow is released.
+    ///
+    /// This is useful for maintaining invariants, such as re-sorting a `Vec`
+    /// after it is mutated. `on_release` runs exactly once when the returned
+    /// guard is dropped, including while unwinding from a panic.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if there are any references to the data already
+    /// in use.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_ref::Cell;
+    ///
+    /// let cell = Cell::new(vec![1, 3]);
+    ///
+    /// {
+    ///     let mut values = cell.borrow_mut_with_finalizer(|values| values.sort());
+    ///     values.push(2);
+    /// }
+    ///
+    /// assert_eq!(vec![1, 2, 3], *cell.borrow());
+    /// ```
+    pub fn borrow_mut_with_finalizer<F>(&self, on_release: F) -> FinalizingRefMut<'_, T, F>
+    where
+        F: FnOnce(&mut T),
+    {
+        FinalizingRefMut {
+            cell_ref_mut: self.borrow_mut(),
+            on_release: Some(on_release),
+        }
+    }
+
+    /// Replaces the inner value, returning the old value.
+    ///
+    /// If the value is currently borrowed, the given `value` is returned
+    /// alongside the `BorrowFail`, so that it is not lost.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_ref::{BorrowFail, Cell};
+    ///
+    /// let cell = Cell::new(1);
+    /// assert_eq!(Ok(1), cell.try_replace(2));
+    ///
+    /// let _r = cell.borrow();
+    /// assert_eq!(
+    ///     Err((3, BorrowFail::ReadLocked(1))),
+    ///     cell.try_replace(3)
+    /// );
+    /// ```
+    pub fn try_replace(&self, value: T) -> Result<T, (T, BorrowFail)> {
+        match self.try_borrow_mut() {
+            Ok(mut cell_ref_mut) => Ok(mem::replace(&mut *cell_ref_mut, value)),
+            Err(e) => Err((value, e)),
+        }
+    }
+
+    /// Takes the inner value, leaving `T::default()` in its place.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if there are any references to the data already
+    /// in use.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_ref::Cell;
+    ///
+    /// let cell = Cell::new(vec![1, 2]);
+    ///
+    /// assert_eq!(vec![1, 2], cell.swap_out());
+    /// assert!(cell.borrow().is_empty());
+    /// ```
+    pub fn swap_out(&self) -> T
+    where
+        T: Default,
+    {
+        mem::take(&mut *self.borrow_mut())
+    }
+
+    /// Swaps the inner value with `value`.
+    ///
+    /// This is the single cell analog of [`try_swap`], and avoids constructing
+    /// a temporary `Cell` to swap with. This may be used for double
+    /// buffering, where a producer fills the buffer in the `Cell` while a
+    /// consumer drains the other buffer.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if there are any references to the data already
+    /// in use.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_ref::Cell;
+    ///
+    /// let cell = Cell::new(1);
+    /// let mut value = 2;
+    ///
+    /// cell.swap_value(&mut value);
+    /// assert_eq!(2, *cell.borrow());
+    /// assert_eq!(1, value);
+    /// ```
+    ///
+    /// [`try_swap`]: Self::try_swap
+    #[doc(alias = "swap_buffers")]
+    pub fn swap_value(&self, value: &mut T) {
+        mem::swap(&mut *self.borrow_mut(), value);
+    }
+
+    /// Sets the inner value to `new` if it is equal to `expected`.
+    ///
+    /// Returns `Ok(())` if the value was set, or `Err(current)` with the
+    /// unchanged value if it was not equal to `expected`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if there are any references to the data already
+    /// in use.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_ref::Cell;
+    ///
+    /// let cell = Cell::new(1);
+    ///
+    /// assert_eq!(Ok(()), cell.compare_and_set(1, 2));
+    /// assert_eq!(Err(2), cell.compare_and_set(1, 3));
+    /// assert_eq!(2, *cell.borrow());
+    /// ```
+    pub fn compare_and_set(&self, expected: T, new: T) -> Result<(), T>
+    where
+        T: Copy + PartialEq,
+    {
+        let mut value = self.borrow_mut();
+        if *value == expected {
+            *value = new;
+            Ok(())
+        } else {
+            Err(*value)
+        }
+    }
+
+    /// Sets the inner value to the value returned by `f`, if it returns
+    /// `Some`.
+    ///
+    /// This mirrors `AtomicUsize::fetch_update`. As the value is borrowed
+    /// mutably, `f` is called exactly once. Returns `Ok(previous)` if the
+    /// value was set, or `Err(current)` if `f` returned `None`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if there are any references to the data already
+    /// in use.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_ref::Cell;
+    ///
+    /// let cell = Cell::new(7u32);
+    ///
+    /// assert_eq!(Ok(7), cell.fetch_update(|n| n.checked_add(1)));
+    /// assert_eq!(Err(8), cell.fetch_update(|n| n.checked_sub(10)));
+    /// assert_eq!(8, *cell.borrow());
+    /// ```
+    pub fn fetch_update<F>(&self, f: F) -> Result<T, T>
+    where
+        T: Copy,
+        F: FnOnce(T) -> Option<T>,
+    {
+        let mut value = self.borrow_mut();
+        let previous = *value;
+        match f(previous) {
+            Some(new) => {
+                *value = new;
+                Ok(previous)
+            }
+            None => Err(previous),
+        }
+    }
+
+    /// Swaps the inner values of this cell and `other`.
+    ///
+    /// Both cells are borrowed mutably, in address order. If either borrow
+    /// fails, any acquired borrow is released, and the failure is returned.
+    ///
+    /// Swapping a cell with itself does nothing, but still fails if the cell
+    /// is borrowed.
+    pub fn try_swap(&self, other: &Cell<T>) -> Result<(), BorrowFail> {
+        if ptr::eq(self, other) {
+            return self.try_borrow_mut().map(|_| ());
+        }
+
+        let (first, second) = if (self as *const Self) < (other as *const Self) {
+            (self, other)
+        } else {
+            (other, self)
+        };
+
+        let mut first = first.try_borrow_mut()?;
+        let mut second = second.try_borrow_mut()?;
+        mem::swap(&mut *first, &mut *second);
+
+        Ok(())
+    }
+
+    /// Moves the inner value of `src` into this cell, leaving `T::default()`
+    /// in `src`.
+    ///
+    /// Both cells are borrowed mutably, in address order. The previous value
+    /// of this cell is dropped.
+    ///
+    /// Taking from the same cell does nothing.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if there are any references to the data in
+    /// either cell already in use.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_ref::Cell;
+    ///
+    /// let a = Cell::new(vec![1]);
+    /// let b = Cell::new(vec![2, 3]);
+    ///
+    /// a.take_from(&b);
+    /// assert_eq!(vec![2, 3], *a.borrow());
+    /// assert!(b.borrow().is_empty());
+    /// ```
+    pub fn take_from(&self, src: &Cell<T>)
+    where
+        T: Default,
+    {
+        if ptr::eq(self, src) {
+            drop(self.borrow_mut());
+            return;
+        }
+
+        let (mut dest, mut src) = if (self as *const Self) < (src as *const Self) {
+            let dest = self.borrow_mut();
+            (dest, src.borrow_mut())
+        } else {
+            let src = src.borrow_mut();
+            (self.borrow_mut(), src)
+        };
+
+        *dest = mem::take(&mut *src);
+    }
+}
+
+impl<T> Cell<T>
+where
+    T: ?Sized,
+{
     /// Get an immutable reference to the inner data.
     ///
     /// Absence of write accesses is checked at run-time.
@@ -45,9 +444,10 @@ impl<T> Cell<T> {
     ///
     /// This function will panic if there is a mutable reference to the data
     /// already in use.
-    pub fn borrow(&self) -> CellRef<T> {
-        if !self.check_flag_read() {
-            borrow_panic!("immutably", " mutably");
+    #[inline]
+    pub fn borrow(&self) -> CellRef<'_, T> {
+        if let Err(borrow_fail) = self.check_flag_read() {
+            borrow_panic!(self, "immutably", self.borrow_existing_imm(borrow_fail));
         }
 
         CellRef {
@@ -56,18 +456,238 @@ impl<T> Cell<T> {
         }
     }
 
+    /// Get an immutable reference to the inner data, which keeps this `Cell`
+    /// alive.
+    ///
+    /// The returned guard holds a clone of the `Arc`, so it may be held after
+    /// this handle is dropped. Absence of write accesses is checked at
+    /// run-time.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if there is a mutable reference to the data
+    /// already in use.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::sync::Arc;
+    ///
+    /// use rt_ref::{Cell, OwnedCellRef};
+    ///
+    /// let cell = Arc::new(Cell::new(1));
+    /// let weak = Arc::downgrade(&cell);
+    ///
+    /// let owned: OwnedCellRef<u32> = cell.borrow_owned();
+    /// drop(cell);
+    /// assert_eq!(1, *owned);
+    ///
+    /// drop(owned);
+    /// assert!(weak.upgrade().is_none());
+    /// ```
+    pub fn borrow_owned(self: &Arc<Self>) -> OwnedCellRef<T> {
+        if let Err(borrow_fail) = self.check_flag_read() {
+            borrow_panic!(self, "immutably", self.borrow_existing_imm(borrow_fail));
+        }
+
+        OwnedCellRef {
+            cell: Arc::clone(self),
+        }
+    }
+
     /// Get an immutable reference to the inner data.
     ///
+    /// Absence of write accesses is checked at run-time. If the value is
+    /// borrowed mutably, `BorrowConflictImm` is returned. If the number of
+    /// immutable borrows has reached the flag limit, `ReadLimitReached` is
+    /// returned.
+    #[inline]
+    pub fn try_borrow(&self) -> Result<CellRef<'_, T>, BorrowFail> {
+        self.check_flag_read().map(|()| CellRef {
+            flag: &self.flag,
+            value: unsafe { &*self.inner.get() },
+        })
+    }
+
+    /// Returns a copy of the inner data.
+    ///
+    /// The `Cell` is borrowed immutably only while the value is copied. If
+    /// the `Cell` is borrowed mutably, `Err` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_ref::{BorrowFail, Cell};
+    ///
+    /// let cell = Cell::new(1);
+    /// assert_eq!(Ok(1), cell.try_get());
+    ///
+    /// let _n = cell.borrow_mut();
+    /// assert_eq!(Err(BorrowFail::BorrowConflictImm), cell.try_get());
+    /// ```
+    pub fn try_get(&self) -> Result<T, BorrowFail>
+    where
+        T: Copy,
+    {
+        self.try_borrow().map(|cell_ref| *cell_ref)
+    }
+
+    /// Get an immutable reference to the inner data, along with a clone of
+    /// the data taken under the same borrow.
+    ///
+    /// This is intended for change detection. While the returned `CellRef` is
+    /// held, the data cannot be mutated, so the live value always equals the
+    /// snapshot. After the `CellRef` is dropped, the snapshot can be compared
+    /// with a later borrow to detect whether the data was changed in between.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if there is a mutable reference to the data
+    /// already in use.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_ref::Cell;
+    ///
+    /// let cell = Cell::new(vec![1, 2]);
+    ///
+    /// let (live, snapshot) = cell.borrow_and_snapshot();
+    /// assert_eq!(*live, snapshot);
+    /// drop(live);
+    ///
+    /// cell.borrow_mut().push(3);
+    /// assert_ne!(*cell.borrow(), snapshot);
+    /// ```
+    pub fn borrow_and_snapshot(&self) -> (CellRef<'_, T>, T)
+    where
+        T: Clone,
+    {
+        let cell_ref = self.borrow();
+        let snapshot = T::clone(&cell_ref);
+
+        (cell_ref, snapshot)
+    }
+
+    /// Get an immutable reference to a component of the inner data.
+    ///
+    /// This is equivalent to `Ref::new(CellRef::map(cell.borrow(), f))`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if there is a mutable reference to the data
+    /// already in use.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_ref::{Cell, Ref};
+    ///
+    /// struct Resource {
+    ///     name: String,
+    /// }
+    ///
+    /// let c = Cell::new(Resource {
+    ///     name: String::from("a"),
+    /// });
+    ///
+    /// let name: Ref<'_, str> = c.borrow_map(|resource| resource.name.as_str());
+    /// assert_eq!("a", &*name);
+    /// ```
+    pub fn borrow_map<U, F>(&self, f: F) -> Ref<'_, U>
+    where
+        F: FnOnce(&T) -> &U,
+        U: ?Sized,
+    {
+        Ref::new(self.borrow().map(f))
+    }
+
+    /// Get an immutable reference to the inner data, and a [`tracing`] span
+    /// that is entered while the reference is held.
+    ///
+    /// The span is named `"rt_ref::borrow"`, and records the borrowed type in
+    /// its `type_name` field. Drop the returned tuple to release the borrow
+    /// and exit the span, so that subscribers see how long the borrow is held.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if there is a mutable reference to the data
+    /// already in use.
+    ///
+    /// [`tracing`]: https://crates.io/crates/tracing
+    #[cfg(feature = "tracing")]
+    pub fn borrow_span(&self) -> (CellRef<'_, T>, EnteredSpan) {
+        let cell_ref = self.borrow();
+        let span = tracing::trace_span!("rt_ref::borrow", type_name = std::any::type_name::<T>());
+
+        (cell_ref, span.entered())
+    }
+
+    /// Get an immutable reference to the inner data, along with information
+    /// about the borrow.
+    ///
+    /// This is intended for building borrow auditing tools.
+    ///
     /// Absence of write accesses is checked at run-time. If access is not
-    /// possible, `None` is returned.
-    pub fn try_borrow(&self) -> Result<CellRef<T>, BorrowFail> {
-        if self.check_flag_read() {
-            Ok(CellRef {
+    /// possible, the failure is returned as for [`try_borrow`].
+    ///
+    /// [`try_borrow`]: Self::try_borrow
+    #[cfg(feature = "track_borrows")]
+    #[track_caller]
+    pub fn try_borrow_info(&self) -> Result<(CellRef<'_, T>, BorrowInfo), BorrowFail> {
+        let location = Location::caller();
+
+        self.check_flag_read_count().map(|readers| {
+            let cell_ref = CellRef {
                 flag: &self.flag,
                 value: unsafe { &*self.inner.get() },
-            })
-        } else {
-            Err(BorrowFail::BorrowConflictImm)
+            };
+
+            (cell_ref, BorrowInfo { readers, location })
+        })
+    }
+
+    /// Get an immutable reference to the inner data, which can be shared with
+    /// nested references without updating the borrow flag.
+    ///
+    /// See [`ReentrantRef`] for usage in recursive traversals.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if there is a mutable reference to the data
+    /// already in use.
+    pub fn borrow_reentrant(&self) -> ReentrantRef<'_, T> {
+        let (raw_borrow, value) = self.borrow().into_raw_parts();
+
+        ReentrantRef {
+            value,
+            flag: Some(raw_borrow.flag),
+        }
+    }
+
+    /// Returns a `CellRef` to the inner data, using the borrow parked in
+    /// `token`.
+    ///
+    /// Unlike [`borrow`], this does not update the borrow flag. See
+    /// [`BorrowToken`] for the reuse protocol.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `token` was not parked from a borrow of this `Cell`.
+    ///
+    /// [`borrow`]: Self::borrow
+    pub fn reuse<'a>(&'a self, token: BorrowToken<'a>) -> CellRef<'a, T> {
+        assert!(
+            ptr::eq(token.flag, &self.flag),
+            "Expected `BorrowToken` to borrow from the given `Cell`."
+        );
+
+        // The token's reader slot is transferred to the returned `CellRef`.
+        mem::forget(token);
+
+        CellRef {
+            flag: &self.flag,
+            value: unsafe { &*self.inner.get() },
         }
     }
 
@@ -79,229 +699,2264 @@ impl<T> Cell<T> {
     ///
     /// This function will panic if there are any references to the data already
     /// in use.
-    pub fn borrow_mut(&self) -> CellRefMut<T> {
-        if !self.check_flag_write() {
-            borrow_panic!("mutably", "");
+    #[inline]
+    pub fn borrow_mut(&self) -> CellRefMut<'_, T> {
+        if self.check_flag_write().is_err() {
+            borrow_panic!(self, "mutably");
         }
 
         CellRefMut {
             flag: &self.flag,
             value: unsafe { &mut *self.inner.get() },
+            shared: false,
         }
     }
 
-    /// Get a mutable reference to the inner data.
+    /// Get a mutable reference to the inner data, which keeps this `Cell`
+    /// alive.
     ///
-    /// Exclusive access is checked at run-time. If access is not possible,
-    /// `None` is returned.
-    pub fn try_borrow_mut(&self) -> Result<CellRefMut<T>, BorrowFail> {
-        if self.check_flag_write() {
-            Ok(CellRefMut {
-                flag: &self.flag,
-                value: unsafe { &mut *self.inner.get() },
-            })
-        } else {
-            Err(BorrowFail::BorrowConflictMut)
+    /// The returned guard holds a clone of the `Arc`, so it may be held after
+    /// this handle is dropped, or moved to another thread. Exclusive access is
+    /// checked at run-time.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if there are any references to the data already
+    /// in use.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::sync::Arc;
+    ///
+    /// use rt_ref::{Cell, OwnedCellRefMut};
+    ///
+    /// let cell = Arc::new(Cell::new(1));
+    ///
+    /// let mut owned: OwnedCellRefMut<u32> = cell.borrow_mut_owned();
+    /// *owned += 1;
+    /// assert!(cell.try_borrow().is_err());
+    ///
+    /// drop(owned);
+    /// assert_eq!(2, *cell.borrow());
+    /// ```
+    pub fn borrow_mut_owned(self: &Arc<Self>) -> OwnedCellRefMut<T> {
+        if self.check_flag_write().is_err() {
+            borrow_panic!(self, "mutably");
+        }
+
+        OwnedCellRefMut {
+            cell: Arc::clone(self),
         }
     }
 
-    /// Gets exclusive access to the inner value, bypassing the Cell.
+    /// Get a mutable reference to a component of the inner data.
     ///
-    /// Exclusive access is checked at compile time.
-    pub fn get_mut(&mut self) -> &mut T {
-        unsafe { &mut *self.inner.get() }
+    /// This is equivalent to `RefMut::new(CellRefMut::map(cell.borrow_mut(),
+    /// f))`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if there are any references to the data already
+    /// in use.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_ref::{Cell, RefMut};
+    ///
+    /// struct Resource {
+    ///     count: u32,
+    /// }
+    ///
+    /// let c = Cell::new(Resource { count: 1 });
+    ///
+    /// let mut count: RefMut<'_, u32> = c.borrow_mut_map(|resource| &mut resource.count);
+    /// *count += 1;
+    /// drop(count);
+    ///
+    /// assert_eq!(2, c.borrow().count);
+    /// ```
+    pub fn borrow_mut_map<U, F>(&self, f: F) -> RefMut<'_, U>
+    where
+        F: FnOnce(&mut T) -> &mut U,
+        U: ?Sized,
+    {
+        RefMut::new(self.borrow_mut().map(f))
+    }
+
+    /// Get a mutable reference to the inner data, recording how long it is
+    /// held.
+    ///
+    /// When the returned guard is dropped, [`max_borrow_mut_duration`] is
+    /// raised to the duration of the borrow, if it is longer. This is useful
+    /// to find long-held borrows that block other borrows of the value.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if there are any references to the data already
+    /// in use.
+    ///
+    /// [`max_borrow_mut_duration`]: Self::max_borrow_mut_duration
+    #[cfg(feature = "timing")]
+    pub fn borrow_mut_timed(&self) -> TimedRefMut<'_, T> {
+        TimedRefMut {
+            cell_ref_mut: self.borrow_mut(),
+            max_nanos: &self.max_borrow_mut_nanos,
+            start: Instant::now(),
+        }
+    }
+
+    /// Get a mutable reference to the inner data.
+    ///
+    /// Exclusive access is checked at run-time. If access is not possible,
+    /// `ReadLocked` with the number of immutable borrows, or `WriteLocked`, is
+    /// returned.
+    #[inline]
+    pub fn try_borrow_mut(&self) -> Result<CellRefMut<'_, T>, BorrowFail> {
+        match self.check_flag_write() {
+            Ok(()) => Ok(CellRefMut {
+                flag: &self.flag,
+                value: unsafe { &mut *self.inner.get() },
+                shared: false,
+            }),
+            Err(flag) => Err(BorrowFail::conflict_mut(flag)),
+        }
+    }
+
+    /// Borrows the inner data mutably and updates it with `f`.
+    ///
+    /// Returns `Err` if the value could not be borrowed, or if `f` returned an
+    /// error. In both cases, the borrow is released before returning.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_ref::{BorrowFail, BorrowFailOr, Cell};
+    ///
+    /// let cell = Cell::new(1u32);
+    ///
+    /// let increment = |n: &mut u32| -> Result<(), &str> {
+    ///     *n = n.checked_add(1).ok_or("overflow")?;
+    ///     Ok(())
+    /// };
+    /// assert_eq!(Ok(()), cell.try_update(increment));
+    /// assert_eq!(2, *cell.borrow());
+    ///
+    /// let _r = cell.borrow();
+    /// assert_eq!(
+    ///     Err(BorrowFailOr::Borrow(BorrowFail::ReadLocked(1))),
+    ///     cell.try_update(increment)
+    /// );
+    /// ```
+    pub fn try_update<E, F>(&self, f: F) -> Result<(), BorrowFailOr<E>>
+    where
+        F: FnOnce(&mut T) -> Result<(), E>,
+    {
+        let mut cell_ref_mut = self.try_borrow_mut()?;
+
+        f(&mut cell_ref_mut).map_err(BorrowFailOr::Update)
+    }
+
+    /// Returns whether the inner values of this cell and `other` are equal.
+    ///
+    /// Both cells are borrowed immutably. If either is borrowed mutably,
+    /// `BorrowConflictImm` is returned instead of panicking.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_ref::{BorrowFail, Cell};
+    ///
+    /// let a = Cell::new(1);
+    /// let b = Cell::new(1);
+    /// assert_eq!(Ok(true), a.try_eq(&b));
+    ///
+    /// let _b_mut = b.borrow_mut();
+    /// assert_eq!(Err(BorrowFail::BorrowConflictImm), a.try_eq(&b));
+    /// ```
+    pub fn try_eq(&self, other: &Cell<T>) -> Result<bool, BorrowFail>
+    where
+        T: PartialEq,
+    {
+        let value = self.try_borrow()?;
+        let value_other = other.try_borrow()?;
+
+        Ok(*value == *value_other)
+    }
+
+    /// Returns whether the inner values of this cell and `other` are equal,
+    /// according to `cmp`.
+    ///
+    /// This allows comparing by a projection, such as an ID field, without
+    /// requiring `T: PartialEq`. As with [`try_eq`], both cells are borrowed
+    /// immutably, and if either is borrowed mutably, `BorrowConflictImm` is
+    /// returned instead of panicking.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_ref::Cell;
+    ///
+    /// let a = Cell::new((1, "a"));
+    /// let b = Cell::new((1, "b"));
+    ///
+    /// assert_eq!(Ok(true), a.eq_by(&b, |a, b| a.0 == b.0));
+    /// assert_eq!(Ok(false), a.eq_by(&b, |a, b| a.1 == b.1));
+    /// ```
+    ///
+    /// [`try_eq`]: Self::try_eq
+    pub fn eq_by<F>(&self, other: &Cell<T>, cmp: F) -> Result<bool, BorrowFail>
+    where
+        F: FnOnce(&T, &T) -> bool,
+    {
+        let value = self.try_borrow()?;
+        let value_other = other.try_borrow()?;
+
+        Ok(cmp(&value, &value_other))
+    }
+
+    /// Get a mutable reference to the inner data, when the caller holds the
+    /// only immutable reference.
+    ///
+    /// The single reader's count is taken over by the returned `CellRefMut`.
+    ///
+    /// If there is not exactly one immutable reference, `ReadLocked` with the
+    /// number of immutable borrows, or `WriteLocked`, is returned.
+    ///
+    /// # Safety
+    ///
+    /// The only outstanding `CellRef` must be owned by the caller, and when
+    /// this returns `Ok`, it must not be used, moved, or dropped afterwards --
+    /// hold it in a `ManuallyDrop` before calling this. Otherwise the
+    /// `CellRef` aliases the mutable reference, and dropping it corrupts the
+    /// borrow flag.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::mem::ManuallyDrop;
+    ///
+    /// use rt_ref::Cell;
+    ///
+    /// let cell = Cell::new(1);
+    ///
+    /// let r = ManuallyDrop::new(cell.borrow());
+    /// assert_eq!(1, **r);
+    ///
+    /// let mut r_mut = unsafe { cell.try_upgrade_from_single_reader() }.unwrap();
+    ///
+    /// *r_mut += 1;
+    /// drop(r_mut);
+    ///
+    /// assert_eq!(2, *cell.borrow());
+    /// ```
+    pub unsafe fn try_upgrade_from_single_reader(&self) -> Result<CellRefMut<'_, T>, BorrowFail> {
+        match self.check_flag_upgrade() {
+            Ok(()) => Ok(CellRefMut {
+                flag: &self.flag,
+                value: unsafe { &mut *self.inner.get() },
+                shared: false,
+            }),
+            Err(flag) => Err(BorrowFail::conflict_mut(flag)),
+        }
+    }
+
+    /// Returns the current value of the borrow flag.
+    ///
+    /// This is intended for instrumentation, such as charting the number of
+    /// readers over time:
+    ///
+    /// * `0` means the value is not borrowed.
+    /// * `usize::MAX` means the value is borrowed mutably.
+    /// * Values above `isize::MAX` mean the mutable borrow is shared by
+    ///   `usize::MAX - flag + 1` `CellRefMut`s, e.g. after
+    ///   `CellRefMut::split_first_mut`.
+    /// * Any other value is the number of immutable borrows.
+    ///
+    /// The value may be stale as soon as it is returned, so it must not be
+    /// used to decide whether a borrow will succeed.
+    pub fn flag_raw(&self) -> usize {
+        self.flag.load(Ordering::Acquire)
+    }
+
+    /// Returns `true` if more than half of this cell's reader limit is in use.
+    ///
+    /// The reader limit is `isize::MAX`, unless a smaller limit is set with
+    /// [`CellBuilder::flag_limit`]. Reaching half of the default limit is not
+    /// a realistic number of live readers, so it indicates that `CellRef`s
+    /// are being leaked, e.g. with `mem::forget`. Long-running services may
+    /// check this to detect the leak before [`CellRef::clone`] fails.
+    ///
+    /// Returns `false` while the value is borrowed mutably.
+    ///
+    /// [`CellBuilder::flag_limit`]: crate::CellBuilder::flag_limit
+    /// [`CellRef::clone`]: crate::CellRef::clone
+    pub fn reader_overflow_imminent(&self) -> bool {
+        let readers = self.flag.load(Ordering::Acquire);
+
+        readers <= REF_LIMIT_MAX && readers > self.flag.limit.max / 2
+    }
+
+    /// Asserts that this cell is not borrowed.
+    ///
+    /// This is intended for teardown assertions in tests, to detect guards
+    /// that were not dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are any references to the data in use.
+    #[cfg(any(test, feature = "testing"))]
+    #[track_caller]
+    pub fn assert_unused(&self) {
+        if self.flag.load(Ordering::Acquire) != 0 {
+            panic!(
+                "Expected `{type_name}` to be unused, but it was borrowed{borrow_existing}",
+                type_name = std::any::type_name::<T>(),
+                borrow_existing = self.borrow_existing(),
+            );
+        }
+    }
+
+    /// Returns the number of borrows of this cell, and of conflicting borrow
+    /// attempts.
+    ///
+    /// Cloning a `CellRef` is not counted as a borrow.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_ref::{Cell, CellStats};
+    ///
+    /// let c = Cell::new(1);
+    ///
+    /// let r = c.borrow();
+    /// assert!(c.try_borrow_mut().is_err());
+    /// drop(r);
+    ///
+    /// assert_eq!(
+    ///     CellStats {
+    ///         borrows: 1,
+    ///         borrows_mut: 0,
+    ///         conflicts: 1,
+    ///     },
+    ///     c.stats()
+    /// );
+    /// ```
+    #[cfg(feature = "metrics")]
+    pub fn stats(&self) -> CellStats {
+        self.stats.snapshot()
+    }
+
+    /// Returns the longest duration that the value was held by a guard from
+    /// [`borrow_mut_timed`].
+    ///
+    /// This is `Duration::ZERO` if no timed borrow has been released.
+    ///
+    /// [`borrow_mut_timed`]: Self::borrow_mut_timed
+    #[cfg(feature = "timing")]
+    pub fn max_borrow_mut_duration(&self) -> Duration {
+        Duration::from_nanos(self.max_borrow_mut_nanos.load(Ordering::Relaxed))
+    }
+
+    /// Returns the highest number of immutable borrows of this `Cell` that
+    /// were held at the same time.
+    ///
+    /// This counts borrows and clones of `CellRef`s. If it keeps growing in a
+    /// long running service, `CellRef`s are likely being leaked.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_ref::Cell;
+    ///
+    /// let c = Cell::new(1);
+    ///
+    /// let r = c.borrow();
+    /// let r_clone = r.clone();
+    /// drop((r, r_clone));
+    /// let _r = c.borrow();
+    ///
+    /// assert_eq!(2, c.max_readers_seen());
+    /// ```
+    #[cfg(feature = "leak_guard")]
+    pub fn max_readers_seen(&self) -> usize {
+        self.flag.max_readers()
+    }
+
+    /// Gets exclusive access to the inner value, bypassing the Cell.
+    ///
+    /// Exclusive access is checked at compile time. For a shared `Cell`, such
+    /// as an `Arc<Cell<T>>`, `&mut Cell<T>` is only available through
+    /// `Arc::get_mut`, which returns `None` while any other handle exists.
+    /// Every guard either borrows a handle, or holds its own handle, such as
+    /// [`OwnedCellRef`], so no guard can exist alongside the `&mut T`.
+    pub fn get_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.inner.get() }
+    }
+
+    /// Make sure we are allowed to acquire a read lock, and increment the read
+    /// count by 1
+    #[inline]
+    fn check_flag_read(&self) -> Result<(), BorrowFail> {
+        self.check_flag_read_count().map(|_| ())
+    }
+
+    /// Make sure we are allowed to acquire a read lock, and increment the read
+    /// count by 1, returning the new read count.
+    #[inline]
+    fn check_flag_read_count(&self) -> Result<usize, BorrowFail> {
+        let readers = self.check_flag_read_count_untracked();
+
+        #[cfg(feature = "metrics")]
+        self.stats.record_borrow(readers.is_ok());
+
+        readers
+    }
+
+    /// Acquires a read lock like `check_flag_read_count`, without recording
+    /// metrics.
+    #[inline]
+    fn check_flag_read_count_untracked(&self) -> Result<usize, BorrowFail> {
+        loop {
+            let val = self.flag.load(Ordering::Acquire);
+
+            if val >= self.flag.limit.max {
+                return Err(BorrowFail::conflict_imm(val));
+            }
+
+            if self
+                .flag
+                .compare_exchange_weak(val, val + 1, Ordering::AcqRel, Ordering::Acquire)
+                == Ok(val)
+            {
+                #[cfg(feature = "leak_guard")]
+                self.flag.record_readers(val + 1);
+
+                return Ok(val + 1);
+            }
+        }
+    }
+
+    /// Make sure we are allowed to acquire a write lock, and then set the write
+    /// lock flag.
+    ///
+    /// On failure, the flag at the time of the attempt is returned.
+    #[inline]
+    fn check_flag_write(&self) -> Result<(), usize> {
+        let acquired = self
+            .flag
+            .compare_exchange(0, usize::MAX, Ordering::AcqRel, Ordering::Acquire)
+            .map(|_| ());
+
+        #[cfg(feature = "track_borrows")]
+        if acquired.is_ok() {
+            self.flag.set_writer();
+        }
+
+        #[cfg(feature = "metrics")]
+        self.stats.record_borrow_mut(acquired.is_ok());
+
+        acquired
+    }
+
+    /// Make sure there is exactly one read lock, and then replace it with the
+    /// write lock flag.
+    ///
+    /// On failure, the flag at the time of the attempt is returned.
+    #[inline]
+    fn check_flag_upgrade(&self) -> Result<(), usize> {
+        let acquired = self
+            .flag
+            .compare_exchange(1, usize::MAX, Ordering::AcqRel, Ordering::Acquire)
+            .map(|_| ());
+
+        #[cfg(feature = "track_borrows")]
+        if acquired.is_ok() {
+            self.flag.set_writer();
+        }
+
+        #[cfg(feature = "metrics")]
+        self.stats.record_borrow_mut(acquired.is_ok());
+
+        acquired
+    }
+
+    /// Returns a description of the existing borrow, used when a borrow
+    /// conflicts.
+    fn borrow_existing(&self) -> String {
+        match self.flag.load(Ordering::Acquire) {
+            0 => String::from("."),
+            1 => String::from(" (1 shared reader)."),
+            flag if flag > REF_LIMIT_MAX => {
+                #[cfg(feature = "track_borrows")]
+                if self.flag.is_writer_current_thread() {
+                    return String::from(" mutably. Re-entrant mutable borrow on the same thread.");
+                }
+
+                String::from(" mutably.")
+            }
+            readers => format!(" ({readers} shared readers)."),
+        }
+    }
+
+    /// Returns a description of the existing borrow, used when an immutable
+    /// borrow fails with `borrow_fail`.
+    fn borrow_existing_imm(&self, borrow_fail: BorrowFail) -> String {
+        match borrow_fail {
+            BorrowFail::ReadLimitReached(1) => {
+                String::from(" (1 shared reader, which is the flag limit).")
+            }
+            BorrowFail::ReadLimitReached(readers) => {
+                format!(" ({readers} shared readers, which is the flag limit).")
+            }
+            _ => self.borrow_existing(),
+        }
+    }
+}
+
+impl<T> Cell<Vec<T>> {
+    /// Extends the inner `Vec` with the contents of the iterator.
+    ///
+    /// This is equivalent to `cell.borrow_mut().extend(iter)`, and releases
+    /// the borrow before returning.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if there are any references to the data already
+    /// in use.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_ref::Cell;
+    ///
+    /// let cell = Cell::new(vec![1, 2]);
+    /// cell.extend_from([3, 4]);
+    ///
+    /// assert_eq!(vec![1, 2, 3, 4], *cell.borrow());
+    /// ```
+    pub fn extend_from<I>(&self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        self.borrow_mut().extend(iter);
+    }
+
+    /// Get an immutable reference to the inner `Vec` as a slice.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if there is a mutable reference to the data
+    /// already in use.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_ref::{Cell, Ref};
+    ///
+    /// let cell = Cell::new(vec![1, 2, 3]);
+    ///
+    /// let slice: Ref<'_, [u32]> = cell.borrow_slice();
+    /// assert_eq!(&[1, 2, 3], &*slice);
+    /// ```
+    pub fn borrow_slice(&self) -> Ref<'_, [T]> {
+        Ref::new(self.borrow().map(Vec::as_slice))
+    }
+
+    /// Get a mutable reference to the inner `Vec` as a slice.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if there are any references to the data already
+    /// in use.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_ref::{Cell, RefMut};
+    ///
+    /// let cell = Cell::new(vec![3, 1, 2]);
+    ///
+    /// let mut slice: RefMut<'_, [u32]> = cell.borrow_slice_mut();
+    /// slice.sort();
+    /// drop(slice);
+    ///
+    /// assert_eq!(vec![1, 2, 3], *cell.borrow());
+    /// ```
+    pub fn borrow_slice_mut(&self) -> RefMut<'_, [T]> {
+        RefMut::new(self.borrow_mut().map(Vec::as_mut_slice))
+    }
+}
+
+impl<T> Cell<T>
+where
+    T: ?Sized + HasLen,
+{
+    /// Returns the length of the inner collection.
+    ///
+    /// The `Cell` is borrowed immutably only while the length is read.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if there is a mutable reference to the data
+    /// already in use.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_ref::Cell;
+    ///
+    /// let cell = Cell::new(vec![1, 2, 3]);
+    /// assert_eq!(3, cell.len());
+    /// ```
+    pub fn len(&self) -> usize {
+        self.borrow().len()
+    }
+
+    /// Returns `true` if the inner collection contains no elements.
+    ///
+    /// The `Cell` is borrowed immutably only while the length is read.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if there is a mutable reference to the data
+    /// already in use.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_ref::Cell;
+    ///
+    /// let cell = Cell::new(String::new());
+    /// assert!(cell.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.borrow().is_empty()
+    }
+}
+
+impl<T> Cell<Option<T>> {
+    /// Get a mutable reference to the inner value, inserting the value
+    /// returned by `f` if it is `None`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if there are any references to the data already
+    /// in use.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_ref::{Cell, RefMut};
+    ///
+    /// let cell = Cell::new(None);
+    ///
+    /// let mut value: RefMut<'_, Vec<u32>> = cell.borrow_mut_or_insert_with(Vec::new);
+    /// value.push(1);
+    /// drop(value);
+    ///
+    /// cell.borrow_mut_or_insert_with(Vec::new).push(2);
+    /// assert_eq!(Some(vec![1, 2]), *cell.borrow());
+    /// ```
+    pub fn borrow_mut_or_insert_with<F>(&self, f: F) -> RefMut<'_, T>
+    where
+        F: FnOnce() -> T,
+    {
+        RefMut::new(self.borrow_mut().map(|value| value.get_or_insert_with(f)))
+    }
+}
+
+impl<T> Cell<[T]> {
+    /// Moves a boxed slice into a new boxed cell.
+    ///
+    /// `Cell::new` requires a sized value. A `Box<Cell<[T; N]>>` coerces to a
+    /// `Box<Cell<[T]>>`, but this is needed when the length is only known at
+    /// run-time.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_ref::Cell;
+    ///
+    /// let values: Box<[u32]> = (1..4).collect();
+    /// let cell: Box<Cell<[u32]>> = Cell::from_boxed_slice(values);
+    ///
+    /// cell.borrow_mut()[0] += 10;
+    /// assert_eq!(&[11, 2, 3], &*cell.borrow());
+    /// ```
+    pub fn from_boxed_slice(values: Box<[T]>) -> Box<Cell<[T]>> {
+        let mut values = values.into_vec();
+        let len = values.len();
+
+        // `Cell` is `repr(C)`, so `Cell<[T; 0]>` has the same header layout as
+        // `Cell<[T]>`: the fields before `inner` have the same types and
+        // offsets, and `inner` is at the same offset, as both `[T; 0]` and
+        // `[T]` have the alignment of `T`. The header is followed by `len`
+        // elements.
+        let inner_offset = mem::offset_of!(Cell<[T; 0]>, inner);
+        let layout = Layout::new::<Cell<[T; 0]>>();
+        let layout = Layout::array::<T>(len)
+            .and_then(|values_layout| {
+                Layout::from_size_align(inner_offset + values_layout.size(), layout.align())
+            })
+            .expect("Expected `Cell<[T]>` size to not overflow `isize::MAX`.")
+            .pad_to_align();
+
+        unsafe {
+            // The layout is never zero sized, as it contains the flag.
+            let ptr = alloc::alloc(layout);
+            if ptr.is_null() {
+                alloc::handle_alloc_error(layout);
+            }
+
+            // Casting the slice pointer retains its length as the metadata of
+            // the `Cell<[T]>` pointer.
+            let cell = ptr::slice_from_raw_parts_mut(ptr.cast::<T>(), len) as *mut Cell<[T]>;
+
+            ptr::addr_of_mut!((*cell).flag).write(Flag::new(0));
+            #[cfg(feature = "metrics")]
+            ptr::addr_of_mut!((*cell).stats).write(CellStatsCounters::default());
+            #[cfg(feature = "timing")]
+            ptr::addr_of_mut!((*cell).max_borrow_mut_nanos).write(AtomicU64::new(0));
+
+            // Move the elements, then free the `Vec`'s buffer without dropping
+            // them.
+            let inner = UnsafeCell::raw_get(ptr::addr_of!((*cell).inner));
+            ptr::copy_nonoverlapping(values.as_ptr(), inner.cast::<T>(), len);
+            values.set_len(0);
+
+            Box::from_raw(cell)
+        }
+    }
+}
+
+#[cfg(not(feature = "unsafe_debug"))]
+impl<T> fmt::Debug for Cell<T>
+where
+    T: ?Sized,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Cell")
+            .field("flag", &self.flag)
+            .field("inner", &&self.inner)
+            .finish()
+    }
+}
+
+#[cfg(feature = "unsafe_debug")]
+impl<T> fmt::Debug for Cell<T>
+where
+    T: fmt::Debug + ?Sized,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Cell")
+            .field("flag", &self.flag)
+            .field("inner", &unsafe { &*self.inner.get() })
+            .finish()
+    }
+}
+
+// `T: Send` is required because a mutable borrow from another thread may move
+// the value out, e.g. through `mem::replace`.
+#[cfg(not(feature = "single_thread"))]
+unsafe impl<T> Sync for Cell<T> where T: ?Sized + Send + Sync {}
+
+/// Returns the inner value of an `Arc<Cell<T>>` if this is the only `Arc`.
+///
+/// Otherwise, the `Arc` is returned unchanged.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::sync::Arc;
+///
+/// use rt_ref::Cell;
+///
+/// let cell = Arc::new(Cell::new(1));
+/// let cell_clone = Arc::clone(&cell);
+///
+/// let cell = rt_ref::try_into_inner(cell).unwrap_err();
+/// drop(cell_clone);
+///
+/// assert_eq!(Some(1), rt_ref::try_into_inner(cell).ok());
+/// ```
+pub fn try_into_inner<T>(arc_cell: Arc<Cell<T>>) -> Result<T, Arc<Cell<T>>> {
+    Arc::try_unwrap(arc_cell).map(Cell::into_inner)
+}
+
+/// Wraps each value in a `Cell`.
+///
+/// # Examples
+///
+/// ```rust
+/// let cells = rt_ref::wrap_all(vec![1, 2]);
+///
+/// *cells[0].borrow_mut() += 10;
+/// assert_eq!(vec![11, 2], rt_ref::unwrap_all(cells));
+/// ```
+pub fn wrap_all<T>(values: Vec<T>) -> Vec<Cell<T>> {
+    values.into_iter().map(Cell::new).collect()
+}
+
+/// Returns the inner value of each `Cell`.
+///
+/// See [`wrap_all`].
+pub fn unwrap_all<T>(cells: Vec<Cell<T>>) -> Vec<T> {
+    cells.into_iter().map(Cell::into_inner).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        mem::ManuallyDrop,
+        panic::{self, AssertUnwindSafe, UnwindSafe},
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+    };
+
+    #[cfg(not(feature = "single_thread"))]
+    use std::{thread, time::Duration};
+
+    use super::{try_into_inner, unwrap_all, wrap_all, Cell};
+    #[cfg(feature = "metrics")]
+    use crate::CellStats;
+    use crate::{
+        cell_ref::REF_LIMIT_MAX, flag::Flag, flag_limit::SATURATED, BorrowFail, BorrowFailOr,
+        CellBuilder, CellRef, CellRefMut, OverflowPolicy, Ref,
+    };
+
+    #[test]
+    fn immutable_guards_are_unwind_safe() {
+        fn assert_unwind_safe<T: UnwindSafe>() {}
+
+        assert_unwind_safe::<CellRef<'_, u32>>();
+        assert_unwind_safe::<Ref<'_, u32>>();
+    }
+
+    #[cfg(feature = "single_thread")]
+    #[test]
+    fn single_thread_cell_is_not_sync() {
+        // Ambiguous, and so fails to compile, if `Cell<u32>: Sync`.
+        trait AmbiguousIfSync<A> {
+            fn some_item() {}
+        }
+        impl<T: ?Sized> AmbiguousIfSync<()> for T {}
+        impl<T: ?Sized + Sync> AmbiguousIfSync<u8> for T {}
+
+        <Cell<u32> as AmbiguousIfSync<_>>::some_item();
+    }
+
+    #[cfg(feature = "single_thread")]
+    #[test]
+    fn single_thread_borrow_and_clone_semantics_are_unchanged() {
+        let cell = Cell::new(1u32);
+
+        let a = cell.borrow();
+        let b = a.clone();
+        assert_eq!(2, cell.flag.load(Ordering::SeqCst));
+        assert_eq!(
+            Err(BorrowFail::ReadLocked(2)),
+            cell.try_borrow_mut().map(|_| ())
+        );
+
+        drop((a, b));
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+
+        let mut c = cell.borrow_mut();
+        *c += 1;
+        assert_eq!(usize::MAX, cell.flag.load(Ordering::SeqCst));
+        assert_eq!(
+            Err(BorrowFail::BorrowConflictImm),
+            cell.try_borrow().map(|_| ())
+        );
+
+        drop(c);
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+        assert_eq!(2, *cell.borrow());
+    }
+
+    #[test]
+    fn swap_out_takes_value_and_leaves_default() {
+        let cell = Cell::new(String::from("a"));
+
+        assert_eq!("a", cell.swap_out());
+        assert_eq!("", *cell.borrow());
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn swap_value_exchanges_cell_and_local_values() {
+        let cell = Cell::new(String::from("cell"));
+        let mut value = String::from("local");
+
+        cell.swap_value(&mut value);
+        assert_eq!("local", *cell.borrow());
+        assert_eq!("cell", value);
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Expected to borrow `u32` mutably, but it was already borrowed (1 shared reader)."
+    )]
+    fn swap_value_panics_when_borrowed() {
+        let cell = Cell::new(1u32);
+
+        let _r = cell.borrow();
+        cell.swap_value(&mut 2);
+    }
+
+    #[test]
+    fn swap_value_exchanges_buffers() {
+        let cell = Cell::new(vec![1u32, 2]);
+        let mut back_buffer = vec![3u32];
+
+        cell.swap_value(&mut back_buffer);
+        assert_eq!(vec![1, 2], back_buffer);
+        assert_eq!(vec![3], *cell.borrow());
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+
+        back_buffer.clear();
+        cell.swap_value(&mut back_buffer);
+        assert_eq!(vec![3], back_buffer);
+        assert!(cell.borrow().is_empty());
+    }
+
+    #[test]
+    fn compare_and_set_sets_value_when_equal() {
+        let cell = Cell::new(1u32);
+
+        assert_eq!(Ok(()), cell.compare_and_set(1, 2));
+        assert_eq!(2, *cell.borrow());
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn compare_and_set_returns_current_value_when_not_equal() {
+        let cell = Cell::new(1u32);
+
+        assert_eq!(Err(1), cell.compare_and_set(2, 3));
+        assert_eq!(1, *cell.borrow());
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Expected to borrow `u32` mutably, but it was already borrowed (1 shared reader)."
+    )]
+    fn compare_and_set_panics_when_borrowed() {
+        let cell = Cell::new(1u32);
+
+        let _r = cell.borrow();
+        let _ = cell.compare_and_set(1, 2);
+    }
+
+    #[test]
+    fn fetch_update_sets_value_when_some() {
+        let cell = Cell::new(1u32);
+
+        assert_eq!(Ok(1), cell.fetch_update(|n| Some(n * 10)));
+        assert_eq!(10, *cell.borrow());
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn fetch_update_keeps_value_when_none() {
+        let cell = Cell::new(u32::MAX);
+
+        assert_eq!(Err(u32::MAX), cell.fetch_update(|n| n.checked_add(1)));
+        assert_eq!(u32::MAX, *cell.borrow());
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Expected to borrow `u32` mutably, but it was already borrowed (1 shared reader)."
+    )]
+    fn fetch_update_panics_when_borrowed() {
+        let cell = Cell::new(1u32);
+
+        let _r = cell.borrow();
+        let _ = cell.fetch_update(Some);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "single_thread", allow(clippy::arc_with_non_send_sync))]
+    fn arc_get_mut_returns_none_while_borrowed_through_clone() {
+        let mut cell = Arc::new(Cell::new(1u32));
+        let cell_clone = Arc::clone(&cell);
+
+        let cell_ref = cell_clone.borrow();
+        assert!(Arc::get_mut(&mut cell).is_none());
+
+        drop(cell_ref);
+        drop(cell_clone);
+        *Arc::get_mut(&mut cell)
+            .expect("Expected `Arc` to be unique.")
+            .get_mut() += 1;
+        assert_eq!(2, *cell.borrow());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "single_thread", allow(clippy::arc_with_non_send_sync))]
+    fn try_into_inner_returns_value_for_unique_arc() {
+        let cell = Arc::new(Cell::new(vec![1u32]));
+
+        assert_eq!(Ok(vec![1]), try_into_inner(cell).map_err(|_| ()));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "single_thread", allow(clippy::arc_with_non_send_sync))]
+    fn try_into_inner_returns_arc_when_shared() {
+        let cell = Arc::new(Cell::new(vec![1u32]));
+        let cell_clone = Arc::clone(&cell);
+
+        let cell = try_into_inner(cell).expect_err("Expected shared `Arc` to be returned.");
+        assert!(Arc::ptr_eq(&cell, &cell_clone));
+        assert_eq!(vec![1], *cell.borrow());
+    }
+
+    #[test]
+    fn wrap_all_and_unwrap_all_round_trip_values() {
+        let values = vec![1u32, 2, 3];
+
+        let cells = wrap_all(values.clone());
+        assert_eq!(3, cells.len());
+        assert!(cells
+            .iter()
+            .all(|cell| cell.flag.load(Ordering::SeqCst) == 0));
+
+        assert_eq!(values, unwrap_all(cells));
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn stats_counts_borrows_and_conflicts() {
+        let cell = Cell::new(1u32);
+
+        let a = cell.borrow();
+        let b = cell
+            .try_borrow()
+            .expect("Expected second borrow to succeed.");
+        let _a_clone = a.clone();
+        assert!(cell.try_borrow_mut().is_err());
+        drop(b);
+
+        assert_eq!(
+            CellStats {
+                borrows: 2,
+                borrows_mut: 0,
+                conflicts: 1,
+            },
+            cell.stats()
+        );
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn stats_counts_mutable_borrows_and_conflicts() {
+        let cell = Cell::new(1u32);
+
+        *cell.borrow_mut() += 1;
+        let c = cell
+            .try_borrow_mut()
+            .expect("Expected mutable borrow to succeed.");
+        assert!(cell.try_borrow().is_err());
+        assert!(cell.try_borrow_mut().is_err());
+        drop(c);
+
+        let _r = ManuallyDrop::new(cell.borrow());
+        let r_mut =
+            unsafe { cell.try_upgrade_from_single_reader() }.expect("Expected upgrade to succeed.");
+        drop(r_mut);
+
+        assert_eq!(
+            CellStats {
+                borrows: 1,
+                borrows_mut: 3,
+                conflicts: 2,
+            },
+            cell.stats()
+        );
+    }
+
+    #[cfg(feature = "leak_guard")]
+    #[test]
+    fn max_readers_seen_records_peak_reader_count() {
+        let cell = Cell::new(1u32);
+        assert_eq!(0, cell.max_readers_seen());
+
+        let r_0 = cell.borrow();
+        let r_1 = cell.borrow();
+        let r_2 = r_1.clone();
+        assert_eq!(3, cell.max_readers_seen());
+
+        drop((r_0, r_1, r_2));
+        let _r = cell.borrow();
+        let _r_mut = cell.try_borrow_mut();
+        assert_eq!(1, cell.flag.load(Ordering::SeqCst));
+        assert_eq!(3, cell.max_readers_seen());
+    }
+
+    #[cfg(feature = "leak_guard")]
+    #[test]
+    fn max_readers_seen_grows_with_leaked_readers() {
+        let cell = Cell::new(1u32);
+
+        (0..5).for_each(|_| std::mem::forget(cell.borrow()));
+        assert_eq!(5, cell.max_readers_seen());
+
+        let r = cell.borrow();
+        assert!(r.try_clone().is_ok());
+        assert_eq!(7, cell.max_readers_seen());
+    }
+
+    #[cfg(feature = "leak_guard")]
+    #[test]
+    fn max_readers_seen_ignores_mutable_borrows() {
+        let cell = Cell::new(1u32);
+
+        let r_mut = cell.borrow_mut();
+        assert!(cell.try_borrow().is_err());
+        drop(r_mut);
+
+        assert_eq!(0, cell.max_readers_seen());
+    }
+
+    #[test]
+    fn extend_from_extends_vec_and_releases_borrow() {
+        let cell = Cell::new(vec![1u32, 2]);
+
+        cell.extend_from((3..5).map(|n| n * 10));
+
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+        assert_eq!(vec![1, 2, 30, 40], *cell.borrow());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Expected to borrow `alloc::vec::Vec<u32>` mutably, but it was already borrowed (1 shared reader)."
+    )]
+    fn extend_from_panics_when_borrowed() {
+        let cell = Cell::new(vec![1u32, 2]);
+
+        let _r = cell.borrow();
+        cell.extend_from([3]);
+    }
+
+    #[test]
+    fn borrow_mut_or_insert_with_inserts_when_none() {
+        let cell = Cell::new(None::<u32>);
+
+        let mut value = cell.borrow_mut_or_insert_with(|| 1);
+        *value += 1;
+        assert_eq!(usize::MAX, cell.flag.load(Ordering::SeqCst));
+
+        drop(value);
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+        assert_eq!(Some(2), *cell.borrow());
+    }
+
+    #[test]
+    fn borrow_mut_or_insert_with_keeps_existing_value() {
+        let cell = Cell::new(Some(5u32));
+
+        let mut value = cell.borrow_mut_or_insert_with(|| panic!("Expected existing value."));
+        *value += 1;
+
+        drop(value);
+        assert_eq!(Some(6), *cell.borrow());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Expected to borrow `core::option::Option<u32>` mutably, but it was already borrowed (1 shared reader)."
+    )]
+    fn borrow_mut_or_insert_with_panics_when_borrowed() {
+        let cell = Cell::new(None::<u32>);
+
+        let _r = cell.borrow();
+        let _value = cell.borrow_mut_or_insert_with(|| 1);
+    }
+
+    #[test]
+    fn borrow_slice_indexes_inner_vec() {
+        let cell = Cell::new(vec![1u32, 2, 3]);
+
+        let slice = cell.borrow_slice();
+        let slice_other = cell.borrow_slice();
+        assert_eq!(2, slice[1]);
+        assert_eq!(3, slice_other.len());
+        assert_eq!(2, cell.flag.load(Ordering::SeqCst));
+
+        drop((slice, slice_other));
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn borrow_slice_mut_mutates_inner_vec() {
+        let cell = Cell::new(vec![1u32, 2, 3]);
+
+        let mut slice = cell.borrow_slice_mut();
+        slice[0] = 4;
+        slice.swap(1, 2);
+        assert_eq!(usize::MAX, cell.flag.load(Ordering::SeqCst));
+
+        drop(slice);
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+        assert_eq!(vec![4, 3, 2], *cell.borrow());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Expected to borrow `alloc::vec::Vec<u32>` mutably, but it was already borrowed (1 shared reader)."
+    )]
+    fn borrow_slice_mut_panics_when_borrowed() {
+        let cell = Cell::new(vec![1u32]);
+
+        let _slice = cell.borrow_slice();
+        let _slice_mut = cell.borrow_slice_mut();
+    }
+
+    #[test]
+    fn len_and_is_empty_release_borrow() {
+        let cell = Cell::new(vec![1u32, 2]);
+
+        assert_eq!(2, cell.len());
+        assert!(!cell.is_empty());
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+
+        cell.borrow_mut().clear();
+        assert_eq!(0, cell.len());
+        assert!(cell.is_empty());
+    }
+
+    #[test]
+    fn len_reads_other_collections() {
+        let cell_string = Cell::new(String::from("abc"));
+        let cell_map = Cell::new(HashMap::from([(1u32, 'a')]));
+        let cell_slice: Box<Cell<[u32]>> = Box::new(Cell::new([1u32, 2, 3, 4]));
+
+        assert_eq!(3, cell_string.len());
+        assert_eq!(1, cell_map.len());
+        assert_eq!(4, cell_slice.len());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Expected to borrow `alloc::vec::Vec<u32>` immutably, but it was already borrowed mutably"
+    )]
+    fn len_panics_when_borrowed_mutably() {
+        let cell = Cell::new(vec![1u32]);
+
+        let _r = cell.borrow_mut();
+        cell.len();
+    }
+
+    #[test]
+    fn try_update_updates_value() {
+        let cell = Cell::new(vec![1u32]);
+
+        let result = cell.try_update(|v| {
+            v.push(2);
+            Ok::<(), ()>(())
+        });
+
+        assert_eq!(Ok(()), result);
+        assert_eq!(vec![1, 2], *cell.borrow());
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn try_update_releases_borrow_when_update_fails() {
+        let cell = Cell::new(vec![1u32]);
+
+        let result = cell.try_update(|v| {
+            v.push(2);
+            Err("partial update")
+        });
+
+        assert_eq!(Err(BorrowFailOr::Update("partial update")), result);
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+        assert_eq!(vec![1, 2], *cell.borrow());
+    }
+
+    #[test]
+    fn try_update_returns_err_when_borrowed() {
+        let cell = Cell::new(1u32);
+
+        let cell_ref_mut = cell.borrow_mut();
+        let result =
+            cell.try_update(|_| -> Result<(), ()> { panic!("Expected `f` to not be called.") });
+
+        assert_eq!(Err(BorrowFailOr::Borrow(BorrowFail::WriteLocked)), result);
+        assert_eq!(usize::MAX, cell.flag.load(Ordering::SeqCst));
+
+        drop(cell_ref_mut);
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn try_eq_returns_true_for_equal_values() {
+        let cell = Cell::new(1u32);
+        let cell_other = Cell::new(1u32);
+
+        assert_eq!(Ok(true), cell.try_eq(&cell_other));
+        assert_eq!(Ok(true), cell.try_eq(&cell));
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+        assert_eq!(0, cell_other.flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn try_eq_returns_false_for_unequal_values() {
+        let cell = Cell::new(1u32);
+        let cell_other = Cell::new(2u32);
+
+        assert_eq!(Ok(false), cell.try_eq(&cell_other));
+    }
+
+    #[test]
+    fn try_eq_returns_err_when_either_is_borrowed_mutably() {
+        let cell = Cell::new(1u32);
+        let cell_other = Cell::new(1u32);
+
+        let cell_other_mut = cell_other.borrow_mut();
+        assert_eq!(Err(BorrowFail::BorrowConflictImm), cell.try_eq(&cell_other));
+        assert_eq!(Err(BorrowFail::BorrowConflictImm), cell_other.try_eq(&cell));
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+        drop(cell_other_mut);
+
+        let _cell_ref = cell.borrow();
+        assert_eq!(Ok(true), cell.try_eq(&cell_other));
+    }
+
+    #[test]
+    fn eq_by_compares_by_field() {
+        struct Entity {
+            id: u32,
+            name: String,
+        }
+
+        let cell = Cell::new(Entity {
+            id: 1,
+            name: String::from("a"),
+        });
+        let cell_other = Cell::new(Entity {
+            id: 1,
+            name: String::from("b"),
+        });
+
+        assert_eq!(Ok(true), cell.eq_by(&cell_other, |a, b| a.id == b.id));
+        assert_eq!(Ok(false), cell.eq_by(&cell_other, |a, b| a.name == b.name));
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+        assert_eq!(0, cell_other.flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn eq_by_returns_err_when_either_is_borrowed_mutably() {
+        let cell = Cell::new(1u32);
+        let cell_other = Cell::new(1u32);
+
+        let cell_other_mut = cell_other.borrow_mut();
+        assert_eq!(
+            Err(BorrowFail::BorrowConflictImm),
+            cell.eq_by(&cell_other, |a, b| a == b)
+        );
+        assert_eq!(
+            Err(BorrowFail::BorrowConflictImm),
+            cell_other.eq_by(&cell, |a, b| a == b)
+        );
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+
+        drop(cell_other_mut);
+        assert_eq!(Ok(true), cell.eq_by(&cell_other, |a, b| a == b));
+    }
+
+    #[test]
+    fn assert_unused_passes_when_guards_are_dropped() {
+        let cell = Cell::new(1u32);
+
+        let cell_ref = cell.borrow();
+        drop(cell_ref);
+        *cell.borrow_mut() += 1;
+
+        cell.assert_unused();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Expected `u32` to be unused, but it was borrowed (1 shared reader)."
+    )]
+    fn assert_unused_panics_when_guard_is_alive() {
+        let cell = Cell::new(1u32);
+
+        let _cell_ref = cell.borrow();
+
+        cell.assert_unused();
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected `u32` to be unused, but it was borrowed mutably.")]
+    fn assert_unused_panics_when_mutable_guard_is_alive() {
+        let cell = Cell::new(1u32);
+
+        let _cell_ref_mut = cell.borrow_mut();
+
+        cell.assert_unused();
+    }
+
+    #[test]
+    fn from_boxed_slice_borrows_slice() {
+        let values: Box<[i32]> = vec![1, 2, 3].into_boxed_slice();
+        let cell: Box<Cell<[i32]>> = Cell::from_boxed_slice(values);
+
+        let cell_ref = cell.borrow();
+        assert_eq!(&[1, 2, 3], &*cell_ref);
+        assert_eq!(1, cell.flag.load(Ordering::SeqCst));
+        drop(cell_ref);
+
+        cell.borrow_mut()[1] = 20;
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+        assert_eq!(&[1, 20, 3], &*cell.borrow());
+    }
+
+    #[test]
+    fn from_boxed_slice_drops_elements_once() {
+        let values: Box<[String]> = vec![String::from("a"), String::from("b")].into_boxed_slice();
+        let cell = Cell::from_boxed_slice(values);
+
+        cell.borrow_mut()[0].push('1');
+        assert_eq!(&[String::from("a1"), String::from("b")], &*cell.borrow());
+    }
+
+    #[test]
+    fn from_boxed_slice_supports_empty_slice() {
+        let cell = Cell::<[u64]>::from_boxed_slice(Box::new([]));
+
+        assert!(cell.borrow().is_empty());
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn catch_unwind_releases_borrow_of_captured_cell() {
+        let cell = Cell::new(1u32);
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            let mut value = cell.borrow_mut();
+            *value += 1;
+            panic!("Expected panic.");
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+        assert_eq!(2, *cell.borrow());
+    }
+
+    #[test]
+    fn allow_multiple_reads() {
+        let cell = Cell::new(5);
+
+        let a = cell.borrow();
+        let b = cell.borrow();
+
+        assert_eq!(10, *a + *b);
+    }
+
+    #[test]
+    fn allow_clone_reads() {
+        let cell = Cell::new(5);
+
+        let a = cell.borrow();
+        let b = a.clone();
+
+        assert_eq!(10, *a + *b);
+    }
+
+    #[test]
+    fn allow_single_write() {
+        let cell = Cell::new(5);
+
+        {
+            let mut a = cell.borrow_mut();
+            *a += 2;
+            *a += 3;
+        }
+
+        assert_eq!(10, *cell.borrow());
+    }
+
+    #[test]
+    fn get_mut_allows_write() {
+        let mut cell = Cell::new(5);
+
+        {
+            let a = cell.get_mut();
+            *a += 2;
+            *a += 3;
+        }
+
+        assert_eq!(10, *cell.borrow());
+    }
+
+    #[test]
+    fn into_inner_returns_value() {
+        #[derive(Debug, PartialEq)]
+        struct A(usize);
+
+        let mut cell = Cell::new(A(5));
+
+        {
+            let a = cell.get_mut();
+            a.0 += 2;
+            a.0 += 3;
+        }
+
+        assert_eq!(A(10), cell.into_inner());
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn borrow_span_enters_span_while_borrowed() {
+        use std::sync::Mutex;
+
+        use tracing::{
+            span::{Attributes, Id, Record},
+            Event, Metadata, Subscriber,
+        };
+
+        /// Records span enters and exits.
+        #[derive(Default)]
+        struct SpanRecorder {
+            events: Mutex<Vec<&'static str>>,
+        }
+
+        impl Subscriber for SpanRecorder {
+            fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+                true
+            }
+
+            fn new_span(&self, attributes: &Attributes<'_>) -> Id {
+                assert_eq!("rt_ref::borrow", attributes.metadata().name());
+                Id::from_u64(1)
+            }
+
+            fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+            fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+            fn event(&self, _event: &Event<'_>) {}
+
+            fn enter(&self, _span: &Id) {
+                self.events.lock().unwrap().push("enter");
+            }
+
+            fn exit(&self, _span: &Id) {
+                self.events.lock().unwrap().push("exit");
+            }
+        }
+
+        let cell = Cell::new(1u32);
+        let recorder = Arc::new(SpanRecorder::default());
+
+        tracing::subscriber::with_default(Arc::clone(&recorder), || {
+            let (cell_ref, span) = cell.borrow_span();
+            assert_eq!(1, *cell_ref);
+            assert_eq!(1, cell.flag_raw());
+            assert_eq!(vec!["enter"], *recorder.events.lock().unwrap());
+
+            drop((cell_ref, span));
+        });
+
+        assert_eq!(vec!["enter", "exit"], *recorder.events.lock().unwrap());
+        assert_eq!(0, cell.flag_raw());
+    }
+
+    #[test]
+    fn try_get_returns_copy_and_releases_borrow() {
+        let cell = Cell::new((1u32, 'a'));
+        let _cell_ref = cell.borrow();
+
+        assert_eq!(Ok((1, 'a')), cell.try_get());
+        assert_eq!(1, cell.flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn borrow_and_snapshot_returns_guard_and_equal_clone() {
+        let cell = Cell::new(vec![1u32, 2]);
+
+        let (live, snapshot) = cell.borrow_and_snapshot();
+        assert_eq!(*live, snapshot);
+        assert_eq!(1, cell.flag.load(Ordering::SeqCst));
+        assert!(cell.try_borrow_mut().is_err());
+
+        drop(live);
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+
+        cell.borrow_mut().push(3);
+        assert_eq!(vec![1, 2], snapshot);
+        assert_ne!(*cell.borrow(), snapshot);
+    }
+
+    #[test]
+    fn try_get_returns_err_when_borrowed_mutably() {
+        let cell = Cell::new(1u32);
+        let cell_ref_mut = cell.borrow_mut();
+
+        assert_eq!(Err(BorrowFail::BorrowConflictImm), cell.try_get());
+        assert_eq!(usize::MAX, cell.flag.load(Ordering::SeqCst));
+
+        drop(cell_ref_mut);
+        assert_eq!(Ok(1), cell.try_get());
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn into_rwlock_returns_lock_with_value() {
+        let cell = Cell::new(vec![1u32]);
+        cell.borrow_mut().push(2);
+
+        let rw_lock = cell.into_rwlock();
+        assert_eq!(
+            vec![1, 2],
+            *rw_lock.read().expect("Expected lock to not be poisoned.")
+        );
+
+        rw_lock
+            .write()
+            .expect("Expected lock to not be poisoned.")
+            .push(3);
+        assert_eq!(
+            vec![1, 2, 3],
+            rw_lock
+                .into_inner()
+                .expect("Expected lock to not be poisoned.")
+        );
+    }
+
+    #[test]
+    fn into_leaked_returns_static_mut() {
+        let cell = Cell::new(vec![1, 2]);
+
+        let leaked: &'static mut Vec<u32> = cell.into_leaked();
+        leaked.push(3);
+
+        assert_eq!(&[1, 2, 3], leaked.as_slice());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Expected to borrow `i32` immutably, but it was already borrowed mutably."
+    )]
+    fn panic_write_and_read() {
+        let cell = Cell::new(5);
+
+        let mut a = cell.borrow_mut();
+        *a = 7;
+
+        assert_eq!(7, *cell.borrow());
+    }
+
+    #[test]
+    fn borrow_map_projects_struct_field() {
+        let cell = Cell::new(Pair {
+            a: 1,
+            b: String::from("b"),
+        });
+
+        let b = cell.borrow_map(|pair| pair.b.as_str());
+        assert_eq!("b", &*b);
+        assert_eq!(1, cell.flag.load(Ordering::SeqCst));
+        assert_eq!(1, cell.borrow().a);
+
+        drop(b);
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Expected to borrow `rt_ref::cell::tests::Pair` immutably, but it was already borrowed mutably."
+    )]
+    fn borrow_map_panics_when_borrowed_mutably() {
+        let cell = Cell::new(Pair {
+            a: 1,
+            b: String::from("b"),
+        });
+
+        let _pair = cell.borrow_mut();
+        let _a = cell.borrow_map(|pair| &pair.a);
+    }
+
+    #[test]
+    fn zst_borrow_and_borrow_mut_enforce_borrow_rules() {
+        let cell = Cell::new(());
+
+        let a = cell.borrow();
+        let b = cell.borrow();
+        assert_eq!(2, cell.flag.load(Ordering::SeqCst));
+        assert_eq!(
+            Err(BorrowFail::ReadLocked(2)),
+            cell.try_borrow_mut().map(|_| ())
+        );
+
+        drop((a, b));
+        let mut c = cell.borrow_mut();
+        *c = ();
+        assert_eq!(usize::MAX, cell.flag.load(Ordering::SeqCst));
+        assert_eq!(
+            Err(BorrowFail::BorrowConflictImm),
+            cell.try_borrow().map(|_| ())
+        );
+        assert_eq!(
+            Err(BorrowFail::WriteLocked),
+            cell.try_borrow_mut().map(|_| ())
+        );
+
+        drop(c);
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+        assert_eq!((), *cell.borrow());
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected to borrow `()` immutably, but it was already borrowed")]
+    fn zst_borrow_panics_when_mutably_borrowed() {
+        let cell = Cell::new(());
+
+        let _a = cell.borrow_mut();
+        let _b = cell.borrow();
+    }
+
+    #[test]
+    fn zst_guards_split_and_map_borrow() {
+        let cell = Cell::new([(); 3]);
+
+        let (first, rest) = cell
+            .borrow_mut()
+            .map(|a| &mut a[..])
+            .split_first_mut()
+            .expect("Expected slice to be non-empty.");
+        assert_eq!(usize::MAX - 1, cell.flag.load(Ordering::SeqCst));
+        assert_eq!(2, rest.len());
+
+        drop((first, rest));
+        let unit = cell.borrow().map(|a| &a[2]);
+        assert_eq!((), *unit);
+
+        drop(unit);
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn zst_from_boxed_slice_enforces_borrow_rules() {
+        let cell = Cell::<[()]>::from_boxed_slice(vec![(); 4].into_boxed_slice());
+
+        let r = cell.borrow();
+        assert_eq!(4, r.len());
+        assert!(cell.try_borrow_mut().is_err());
+
+        drop(r);
+        assert_eq!(4, cell.borrow_mut().len());
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn borrow_mut_map_mutates_struct_field() {
+        let cell = Cell::new(Pair {
+            a: 1,
+            b: String::from("b"),
+        });
+
+        let mut b = cell.borrow_mut_map(|pair| pair.b.as_mut_str());
+        b.make_ascii_uppercase();
+        assert_eq!(usize::MAX, cell.flag.load(Ordering::SeqCst));
+
+        drop(b);
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+        assert_eq!("B", cell.borrow().b);
+        assert_eq!(1, cell.borrow().a);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Expected to borrow `rt_ref::cell::tests::Pair` mutably, but it was already borrowed (1 shared reader)."
+    )]
+    fn borrow_mut_map_panics_when_borrowed_immutably() {
+        let cell = Cell::new(Pair {
+            a: 1,
+            b: String::from("b"),
+        });
+
+        let _pair = cell.borrow();
+        let _a = cell.borrow_mut_map(|pair| &mut pair.a);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Expected to borrow `i32` mutably, but it was already borrowed mutably."
+    )]
+    fn panic_write_and_write() {
+        let cell = Cell::new(5);
+
+        let mut a = cell.borrow_mut();
+        *a = 7;
+
+        assert_eq!(7, *cell.borrow_mut());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Expected to borrow `i32` mutably, but it was already borrowed (1 shared reader)."
+    )]
+    fn panic_read_and_write() {
+        let cell = Cell::new(5);
+
+        let _a = cell.borrow();
+
+        assert_eq!(7, *cell.borrow_mut());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Expected to borrow `i32` mutably, but it was already borrowed (3 shared readers)."
+    )]
+    fn panic_reads_and_write() {
+        let cell = Cell::new(5);
+
+        let _a = cell.borrow();
+        let _b = cell.borrow();
+        let _c = cell.borrow();
+
+        assert_eq!(7, *cell.borrow_mut());
+    }
+
+    #[cfg(feature = "track_borrows")]
+    #[test]
+    #[should_panic(
+        expected = "Expected to borrow `i32` immutably, but it was already borrowed mutably. \
+            Re-entrant mutable borrow on the same thread."
+    )]
+    fn panic_write_and_read_same_thread() {
+        let cell = Cell::new(5);
+
+        let _a = cell.borrow_mut();
+
+        let _b = cell.borrow();
+    }
+
+    #[cfg(feature = "track_borrows")]
+    #[test]
+    #[should_panic(
+        expected = "Expected to borrow `i32` mutably, but it was already borrowed mutably. \
+            Re-entrant mutable borrow on the same thread."
+    )]
+    fn panic_write_and_write_same_thread() {
+        let cell = Cell::new(5);
+
+        let _a = cell.borrow_mut();
+
+        let _b = cell.borrow_mut();
+    }
+
+    #[cfg(all(feature = "track_borrows", not(feature = "single_thread")))]
+    #[test]
+    fn panic_write_and_write_other_thread_is_not_reentrant() {
+        let cell = Cell::new(5);
+
+        let _a = cell.borrow_mut();
+
+        let panic_payload = thread::scope(|s| {
+            s.spawn(|| {
+                let _b = cell.borrow_mut();
+            })
+            .join()
+            .expect_err("Expected `borrow_mut` to panic.")
+        });
+
+        assert_eq!(
+            Some("Expected to borrow `i32` mutably, but it was already borrowed mutably."),
+            panic_payload.downcast_ref::<String>().map(String::as_str)
+        );
+    }
+
+    #[cfg(feature = "track_borrows")]
+    #[test]
+    fn released_write_is_not_reentrant() {
+        let cell = Cell::new(5);
+
+        drop(cell.borrow_mut());
+
+        // Another thread acquires the mutable borrow, but has not recorded
+        // itself as the writer yet.
+        cell.flag.store(usize::MAX, Ordering::SeqCst);
+
+        let panic_payload = panic::catch_unwind(AssertUnwindSafe(|| {
+            let _b = cell.borrow();
+        }))
+        .expect_err("Expected `borrow` to panic.");
+
+        assert_eq!(
+            Some("Expected to borrow `i32` immutably, but it was already borrowed mutably."),
+            panic_payload.downcast_ref::<String>().map(String::as_str)
+        );
+    }
+
+    #[cfg(feature = "track_borrows")]
+    #[test]
+    fn frozen_write_is_not_reentrant() {
+        let cell = Cell::new(5);
+
+        drop(cell.borrow_mut().freeze());
+        cell.flag.store(usize::MAX, Ordering::SeqCst);
+
+        let panic_payload = panic::catch_unwind(AssertUnwindSafe(|| {
+            let _b = cell.borrow_mut();
+        }))
+        .expect_err("Expected `borrow_mut` to panic.");
+
+        assert_eq!(
+            Some("Expected to borrow `i32` mutably, but it was already borrowed mutably."),
+            panic_payload.downcast_ref::<String>().map(String::as_str)
+        );
+    }
+
+    #[cfg(feature = "track_borrows")]
+    #[test]
+    fn try_borrow_info_returns_readers_and_location() {
+        let cell = Cell::new(5);
+
+        let line = line!() + 1;
+        let (a, a_info) = cell.try_borrow_info().unwrap();
+        let (b, b_info) = cell.try_borrow_info().unwrap();
+
+        assert_eq!(1, a_info.readers);
+        assert_eq!(2, b_info.readers);
+        assert_eq!(file!(), a_info.location.file());
+        assert_eq!(line, a_info.location.line());
+        assert_eq!(line + 1, b_info.location.line());
+
+        drop(a);
+        let (_c, c_info) = cell.try_borrow_info().unwrap();
+        assert_eq!(2, c_info.readers);
+        assert_eq!(5, *b);
+    }
+
+    #[cfg(feature = "track_borrows")]
+    #[test]
+    fn try_borrow_info_fails_when_borrowed_mutably() {
+        let cell = Cell::new(5);
+
+        let _a = cell.borrow_mut();
+
+        assert_eq!(
+            BorrowFail::BorrowConflictImm,
+            cell.try_borrow_info().unwrap_err()
+        );
+    }
+
+    #[test]
+    fn try_write_and_read() {
+        let cell = Cell::new(5);
+
+        let mut a = cell.try_borrow_mut().unwrap();
+        *a = 7;
+
+        assert_eq!(
+            BorrowFail::BorrowConflictImm,
+            cell.try_borrow().unwrap_err()
+        );
+
+        *a = 8;
+    }
+
+    #[test]
+    fn try_write_and_write() {
+        let cell = Cell::new(5);
+
+        let mut a = cell.try_borrow_mut().unwrap();
+        *a = 7;
+
+        assert_eq!(BorrowFail::WriteLocked, cell.try_borrow_mut().unwrap_err());
+
+        *a = 8;
     }
 
-    /// Make sure we are allowed to acquire a read lock, and increment the read
-    /// count by 1
-    fn check_flag_read(&self) -> bool {
-        loop {
-            let val = self.flag.load(Ordering::Acquire);
+    #[test]
+    fn try_read_and_write() {
+        let cell = Cell::new(5);
 
-            if val >= REF_LIMIT_MAX {
-                return false;
-            }
+        let _a = cell.try_borrow().unwrap();
 
-            if self
-                .flag
-                .compare_exchange_weak(val, val + 1, Ordering::AcqRel, Ordering::Acquire)
-                == Ok(val)
-            {
-                return true;
-            }
-        }
+        assert_eq!(
+            BorrowFail::ReadLocked(1),
+            cell.try_borrow_mut().unwrap_err()
+        );
     }
 
-    /// Make sure we are allowed to acquire a write lock, and then set the write
-    /// lock flag.
-    fn check_flag_write(&self) -> bool {
-        self.flag
-            .compare_exchange(0, usize::MAX, Ordering::AcqRel, Ordering::Acquire)
-            == Ok(0)
-    }
-}
+    #[test]
+    fn borrow_mut_with_finalizer_runs_finalizer_before_release() {
+        let cell = Cell::new(vec![3, 1]);
+        let finalizer_runs = AtomicUsize::new(0);
 
-#[cfg(feature = "unsafe_debug")]
-use std::fmt;
+        {
+            let mut values = cell.borrow_mut_with_finalizer(|values| {
+                assert_eq!(cell.flag.load(Ordering::SeqCst), usize::MAX);
+                finalizer_runs.fetch_add(1, Ordering::SeqCst);
+                values.sort();
+            });
+            values.push(2);
+
+            assert_eq!(0, finalizer_runs.load(Ordering::SeqCst));
+        }
 
-#[cfg(feature = "unsafe_debug")]
-impl<T> fmt::Debug for Cell<T>
-where
-    T: fmt::Debug,
-{
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("Cell")
-            .field("flag", &self.flag)
-            .field("inner", unsafe { &*self.inner.get() })
-            .finish()
+        assert_eq!(1, finalizer_runs.load(Ordering::SeqCst));
+        assert_eq!(cell.flag.load(Ordering::SeqCst), 0);
+        assert_eq!(vec![1, 2, 3], *cell.borrow());
     }
-}
 
-unsafe impl<T> Sync for Cell<T> where T: Sync {}
+    #[test]
+    fn borrow_mut_with_finalizer_runs_finalizer_when_unwinding() {
+        let cell = Cell::new(vec![3, 1]);
 
-#[cfg(test)]
-mod tests {
-    use std::sync::atomic::{AtomicUsize, Ordering};
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            let mut values = cell.borrow_mut_with_finalizer(|values| values.sort());
+            values.push(2);
+
+            panic!("Expected panic.");
+        }));
 
-    use super::Cell;
-    use crate::{BorrowFail, CellRef, CellRefMut};
+        assert!(result.is_err());
+        assert_eq!(cell.flag.load(Ordering::SeqCst), 0);
+        assert_eq!(vec![1, 2, 3], *cell.borrow());
+    }
 
     #[test]
-    fn allow_multiple_reads() {
+    fn try_replace_returns_old_value() {
         let cell = Cell::new(5);
 
-        let a = cell.borrow();
-        let b = cell.borrow();
-
-        assert_eq!(10, *a + *b);
+        assert_eq!(Ok(5), cell.try_replace(7));
+        assert_eq!(7, *cell.borrow());
+        assert_eq!(cell.flag.load(Ordering::SeqCst), 0);
     }
 
     #[test]
-    fn allow_clone_reads() {
-        let cell = Cell::new(5);
+    fn try_replace_returns_value_when_borrowed() {
+        let cell = Cell::new(vec![5]);
 
         let a = cell.borrow();
-        let b = a.clone();
 
-        assert_eq!(10, *a + *b);
+        let (value, e) = cell.try_replace(vec![7]).unwrap_err();
+        assert_eq!(vec![7], value);
+        assert_eq!(BorrowFail::ReadLocked(1), e);
+        assert_eq!(vec![5], *a);
     }
 
     #[test]
-    fn allow_single_write() {
-        let cell = Cell::new(5);
+    fn try_swap_swaps_values() {
+        let a = Cell::new(5);
+        let b = Cell::new(7);
+
+        assert_eq!(Ok(()), a.try_swap(&b));
+        assert_eq!(Ok(()), b.try_swap(&a));
+        assert_eq!(Ok(()), b.try_swap(&a));
+
+        assert_eq!(7, *a.borrow());
+        assert_eq!(5, *b.borrow());
+        assert_eq!(a.flag.load(Ordering::SeqCst), 0);
+        assert_eq!(b.flag.load(Ordering::SeqCst), 0);
+    }
 
-        {
-            let mut a = cell.borrow_mut();
-            *a += 2;
-            *a += 3;
-        }
+    #[test]
+    fn try_swap_fails_when_other_borrowed() {
+        let a = Cell::new(5);
+        let b = Cell::new(7);
 
-        assert_eq!(10, *cell.borrow());
+        let b_ref = b.borrow();
+
+        assert_eq!(Err(BorrowFail::ReadLocked(1)), a.try_swap(&b));
+        assert_eq!(Err(BorrowFail::ReadLocked(1)), b.try_swap(&a));
+        assert_eq!(a.flag.load(Ordering::SeqCst), 0);
+        assert_eq!(b.flag.load(Ordering::SeqCst), 1);
+
+        drop(b_ref);
+        assert_eq!(5, *a.borrow());
+        assert_eq!(7, *b.borrow());
     }
 
     #[test]
-    fn get_mut_allows_write() {
-        let mut cell = Cell::new(5);
+    fn take_from_moves_value_and_leaves_default() {
+        let a = Cell::new(String::from("a"));
+        let b = Cell::new(String::from("b"));
+
+        a.take_from(&b);
+        assert_eq!("b", *a.borrow());
+        assert_eq!("", *b.borrow());
+
+        b.take_from(&a);
+        assert_eq!("", *a.borrow());
+        assert_eq!("b", *b.borrow());
+        assert_eq!(0, a.flag.load(Ordering::SeqCst));
+        assert_eq!(0, b.flag.load(Ordering::SeqCst));
+    }
 
-        {
-            let a = cell.get_mut();
-            *a += 2;
-            *a += 3;
-        }
+    #[test]
+    fn take_from_self_does_nothing() {
+        let a = Cell::new(5);
 
-        assert_eq!(10, *cell.borrow());
+        a.take_from(&a);
+        assert_eq!(5, *a.borrow());
+        assert_eq!(0, a.flag.load(Ordering::SeqCst));
     }
 
     #[test]
-    fn into_inner_returns_value() {
-        #[derive(Debug, PartialEq)]
-        struct A(usize);
+    fn take_from_panics_and_releases_borrows_when_src_borrowed() {
+        let a = Cell::new(5);
+        let b = Cell::new(7);
 
-        let mut cell = Cell::new(A(5));
+        let b_ref = b.borrow();
+        let result = panic::catch_unwind(AssertUnwindSafe(|| a.take_from(&b)));
 
-        {
-            let a = cell.get_mut();
-            a.0 += 2;
-            a.0 += 3;
-        }
+        assert!(result.is_err());
+        assert_eq!(0, a.flag.load(Ordering::SeqCst));
+        assert_eq!(1, b.flag.load(Ordering::SeqCst));
 
-        assert_eq!(A(10), cell.into_inner());
+        drop(b_ref);
+        assert_eq!(5, *a.borrow());
+        assert_eq!(7, *b.borrow());
     }
 
     #[test]
     #[should_panic(
-        expected = "Expected to borrow `i32` immutably, but it was already borrowed mutably."
+        expected = "Expected to borrow `i32` mutably, but it was already borrowed mutably"
     )]
-    fn panic_write_and_read() {
+    fn take_from_panics_when_dest_borrowed() {
+        let a = Cell::new(5);
+        let b = Cell::new(7);
+
+        let _a_mut = a.borrow_mut();
+        a.take_from(&b);
+    }
+
+    #[test]
+    fn try_swap_with_self_does_nothing() {
+        let a = Cell::new(5);
+
+        assert_eq!(Ok(()), a.try_swap(&a));
+        assert_eq!(5, *a.borrow());
+
+        let _a_ref = a.borrow();
+        assert_eq!(Err(BorrowFail::ReadLocked(1)), a.try_swap(&a));
+    }
+
+    #[test]
+    fn try_upgrade_from_single_reader_succeeds_when_one_reader() {
         let cell = Cell::new(5);
 
-        let mut a = cell.borrow_mut();
-        *a = 7;
+        let _a = ManuallyDrop::new(cell.borrow());
+        let mut b = unsafe { cell.try_upgrade_from_single_reader() }.unwrap();
 
+        assert_eq!(cell.flag.load(Ordering::SeqCst), usize::MAX);
+        *b = 7;
+        drop(b);
+
+        assert_eq!(cell.flag.load(Ordering::SeqCst), 0);
         assert_eq!(7, *cell.borrow());
     }
 
     #[test]
-    #[should_panic(expected = "Expected to borrow `i32` mutably, but it was already borrowed.")]
-    fn panic_write_and_write() {
+    fn try_upgrade_from_single_reader_fails_when_no_readers() {
         let cell = Cell::new(5);
 
-        let mut a = cell.borrow_mut();
-        *a = 7;
-
-        assert_eq!(7, *cell.borrow_mut());
+        assert_eq!(
+            BorrowFail::ReadLocked(0),
+            unsafe { cell.try_upgrade_from_single_reader() }.unwrap_err()
+        );
+        assert_eq!(cell.flag.load(Ordering::SeqCst), 0);
     }
 
     #[test]
-    #[should_panic(expected = "Expected to borrow `i32` mutably, but it was already borrowed.")]
-    fn panic_read_and_write() {
+    fn try_upgrade_from_single_reader_fails_when_multiple_readers() {
         let cell = Cell::new(5);
 
         let _a = cell.borrow();
+        let _b = cell.borrow();
 
-        assert_eq!(7, *cell.borrow_mut());
+        assert_eq!(
+            BorrowFail::ReadLocked(2),
+            unsafe { cell.try_upgrade_from_single_reader() }.unwrap_err()
+        );
+        assert_eq!(cell.flag.load(Ordering::SeqCst), 2);
     }
 
     #[test]
-    fn try_write_and_read() {
+    fn flag_raw_returns_borrow_flag() {
         let cell = Cell::new(5);
+        assert_eq!(0, cell.flag_raw());
 
-        let mut a = cell.try_borrow_mut().unwrap();
-        *a = 7;
+        let a = cell.borrow();
+        assert_eq!(1, cell.flag_raw());
 
-        assert_eq!(
-            BorrowFail::BorrowConflictImm,
-            cell.try_borrow().unwrap_err()
-        );
+        let b = a.clone();
+        assert_eq!(2, cell.flag_raw());
 
-        *a = 8;
+        drop(a);
+        drop(b);
+        assert_eq!(0, cell.flag_raw());
+
+        let c = cell.borrow_mut();
+        assert_eq!(usize::MAX, cell.flag_raw());
+
+        drop(c);
+        assert_eq!(0, cell.flag_raw());
     }
 
     #[test]
-    fn try_write_and_write() {
-        let cell = Cell::new(5);
+    fn reader_overflow_imminent_returns_false_for_few_readers() {
+        let mut cell = Cell::new(5);
+        assert!(!cell.reader_overflow_imminent());
 
-        let mut a = cell.try_borrow_mut().unwrap();
-        *a = 7;
+        let a = cell.borrow();
+        let b = cell.borrow();
+        assert!(!cell.reader_overflow_imminent());
+        drop((a, b));
 
-        assert_eq!(
-            BorrowFail::BorrowConflictMut,
-            cell.try_borrow_mut().unwrap_err()
-        );
+        cell.flag = Flag::new(REF_LIMIT_MAX / 2);
+        assert!(!cell.reader_overflow_imminent());
+    }
 
-        *a = 8;
+    #[test]
+    fn reader_overflow_imminent_returns_true_near_limit() {
+        let mut cell = Cell::new(5);
+
+        cell.flag = Flag::new(REF_LIMIT_MAX / 2 + 1);
+        assert!(cell.reader_overflow_imminent());
+
+        cell.flag = Flag::new(REF_LIMIT_MAX);
+        assert!(cell.reader_overflow_imminent());
     }
 
     #[test]
-    fn try_read_and_write() {
+    fn reader_overflow_imminent_returns_false_when_borrowed_mutably() {
         let cell = Cell::new(5);
 
-        let _a = cell.try_borrow().unwrap();
+        let _a = cell.borrow_mut();
+        assert!(!cell.reader_overflow_imminent());
+    }
 
-        assert_eq!(
-            BorrowFail::BorrowConflictMut,
-            cell.try_borrow_mut().unwrap_err()
-        );
+    #[test]
+    fn reader_overflow_imminent_uses_flag_limit() {
+        let cell = CellBuilder::new(5).flag_limit(4).build();
+
+        let a = cell.borrow();
+        let b = cell.borrow();
+        assert!(!cell.reader_overflow_imminent());
+
+        let c = cell.borrow();
+        assert!(cell.reader_overflow_imminent());
+
+        drop((a, b, c));
     }
 
     #[test]
@@ -314,7 +2969,7 @@ mod tests {
         drop(a);
 
         assert_eq!(
-            BorrowFail::BorrowConflictMut,
+            BorrowFail::ReadLocked(1),
             cell.try_borrow_mut().unwrap_err()
         );
         assert_eq!(5, *b);
@@ -323,7 +2978,7 @@ mod tests {
     #[test]
     fn ref_with_non_sized() {
         let r: CellRef<'_, [i32]> = CellRef {
-            flag: &AtomicUsize::new(1),
+            flag: &Flag::new(1),
             value: &[2, 3, 4, 5][..],
         };
 
@@ -333,7 +2988,7 @@ mod tests {
     #[test]
     fn ref_with_non_sized_clone() {
         let r: CellRef<'_, [i32]> = CellRef {
-            flag: &AtomicUsize::new(1),
+            flag: &Flag::new(1),
             value: &[2, 3, 4, 5][..],
         };
         let rr = r.clone();
@@ -348,18 +3003,43 @@ mod tests {
     #[test]
     fn ref_with_trait_obj() {
         let ra: CellRef<'_, dyn std::any::Any> = CellRef {
-            flag: &AtomicUsize::new(1),
+            flag: &Flag::new(1),
             value: &2i32,
         };
 
         assert_eq!(ra.downcast_ref::<i32>().unwrap(), &2i32);
     }
 
+    #[test]
+    fn borrow_any_downcasts_to_concrete_type() {
+        let cell = Cell::new(vec![1u32, 2]);
+
+        let value = cell.borrow_any();
+        assert_eq!(1, cell.flag.load(Ordering::SeqCst));
+        assert_eq!(Some(&vec![1u32, 2]), value.downcast_ref::<Vec<u32>>());
+        assert!(value.downcast_ref::<Vec<i32>>().is_none());
+
+        drop(value);
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Expected to borrow `u32` immutably, but it was already borrowed mutably."
+    )]
+    fn borrow_any_panics_when_borrowed_mutably() {
+        let cell = Cell::new(1u32);
+
+        let _value_mut = cell.borrow_mut();
+        let _value = cell.borrow_any();
+    }
+
     #[test]
     fn ref_mut_with_non_sized() {
         let mut r: CellRefMut<'_, [i32]> = CellRefMut {
-            flag: &AtomicUsize::new(1),
+            flag: &Flag::new(1),
             value: &mut [2, 3, 4, 5][..],
+            shared: false,
         };
 
         assert_eq!(&mut *r, &mut [2, 3, 4, 5][..]);
@@ -368,8 +3048,9 @@ mod tests {
     #[test]
     fn ref_mut_with_trait_obj() {
         let mut ra: CellRefMut<'_, dyn std::any::Any> = CellRefMut {
-            flag: &AtomicUsize::new(1),
+            flag: &Flag::new(1),
             value: &mut 2i32,
+            shared: false,
         };
 
         assert_eq!(ra.downcast_mut::<i32>().unwrap(), &mut 2i32);
@@ -418,6 +3099,28 @@ mod tests {
         assert_eq!(cell.flag.load(Ordering::SeqCst), 0);
     }
 
+    #[test]
+    fn ref_and_then_some_retains_borrow() {
+        let cell = Cell::new(vec![1u32, 2, 3]);
+
+        let r: Option<CellRef<'_, u32>> = cell.borrow().and_then(|v| v.get(1));
+
+        assert_eq!(Some(2), r.as_deref().copied());
+        assert_eq!(cell.flag.load(Ordering::SeqCst), 1);
+        drop(r);
+        assert_eq!(cell.flag.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn ref_and_then_none_releases_borrow() {
+        let cell = Cell::new(vec![1u32, 2, 3]);
+
+        let r: Option<CellRef<'_, u32>> = cell.borrow().and_then(|v| v.get(3));
+
+        assert!(r.is_none());
+        assert_eq!(cell.flag.load(Ordering::SeqCst), 0);
+    }
+
     #[test]
     fn ref_mut_map_box() {
         let cell = Cell::new(Box::new(10));
@@ -437,14 +3140,14 @@ mod tests {
         let cell = Cell::new(Box::new(10));
 
         let r: CellRefMut<'_, Box<usize>> = cell.borrow_mut();
-        assert_eq!(cell.flag.load(Ordering::SeqCst), std::usize::MAX);
+        assert_eq!(cell.flag.load(Ordering::SeqCst), usize::MAX);
         let _nr: CellRefMut<'_, usize> = r.map(Box::as_mut);
-        assert_eq!(cell.flag.load(Ordering::SeqCst), std::usize::MAX);
+        assert_eq!(cell.flag.load(Ordering::SeqCst), usize::MAX);
     }
 
     #[test]
     #[should_panic(
-        expected = "Expected to borrow `alloc::boxed::Box<usize>` mutably, but it was already borrowed."
+        expected = "Expected to borrow `alloc::boxed::Box<usize>` mutably, but it was already borrowed mutably."
     )]
     fn ref_mut_map_retains_mut_borrow() {
         let cell = Cell::new(Box::new(10));
@@ -460,11 +3163,139 @@ mod tests {
 
         let r: CellRefMut<'_, usize> = cell.borrow_mut().map(Box::as_mut);
 
-        assert_eq!(cell.flag.load(Ordering::SeqCst), std::usize::MAX);
+        assert_eq!(cell.flag.load(Ordering::SeqCst), usize::MAX);
+        drop(r);
+        assert_eq!(cell.flag.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn ref_mut_and_then_some_retains_borrow() {
+        let cell = Cell::new(vec![1u32, 2, 3]);
+
+        let r: Option<CellRefMut<'_, u32>> = cell.borrow_mut().and_then(|v| v.get_mut(1));
+
+        assert_eq!(Some(2), r.as_deref().copied());
+        assert_eq!(cell.flag.load(Ordering::SeqCst), usize::MAX);
         drop(r);
         assert_eq!(cell.flag.load(Ordering::SeqCst), 0);
     }
 
+    #[test]
+    fn ref_mut_and_then_none_releases_borrow() {
+        let cell = Cell::new(vec![1u32, 2, 3]);
+
+        let r: Option<CellRefMut<'_, u32>> = cell.borrow_mut().and_then(|v| v.get_mut(3));
+
+        assert!(r.is_none());
+        assert_eq!(cell.flag.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn ref_mut_freeze_allows_reads() {
+        let cell = Cell::new(5);
+
+        let mut r = cell.borrow_mut();
+        *r = 6;
+
+        let frozen = r.freeze();
+        assert_eq!(cell.flag.load(Ordering::SeqCst), 1);
+
+        let a = cell.try_borrow().unwrap();
+        assert_eq!(6, *a);
+        assert_eq!(6, *frozen);
+        assert_eq!(cell.flag.load(Ordering::SeqCst), 2);
+        assert_eq!(
+            BorrowFail::ReadLocked(2),
+            cell.try_borrow_mut().unwrap_err()
+        );
+        drop(a);
+
+        let mut r = frozen.unfreeze();
+        assert_eq!(cell.flag.load(Ordering::SeqCst), usize::MAX);
+        assert_eq!(
+            BorrowFail::BorrowConflictImm,
+            cell.try_borrow().unwrap_err()
+        );
+
+        *r = 7;
+        drop(r);
+
+        assert_eq!(cell.flag.load(Ordering::SeqCst), 0);
+        assert_eq!(7, *cell.borrow());
+    }
+
+    #[test]
+    fn ref_mut_freeze_drop_releases_borrow() {
+        let cell = Cell::new(5);
+
+        let frozen = cell.borrow_mut().freeze();
+        assert_eq!(cell.flag.load(Ordering::SeqCst), 1);
+
+        drop(frozen);
+        assert_eq!(cell.flag.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn ref_mut_freeze_drop_keeps_saturated_borrow() {
+        let cell = CellBuilder::new(5)
+            .flag_limit(2)
+            .on_overflow(OverflowPolicy::Saturate)
+            .build();
+
+        let frozen = cell.borrow_mut().freeze();
+        let a = cell.borrow();
+        let b = a.clone();
+        assert_eq!(SATURATED, cell.flag.load(Ordering::SeqCst));
+
+        drop(frozen);
+        drop((a, b));
+        assert_eq!(SATURATED, cell.flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot unfreeze a `FrozenGuard` whose borrow is saturated.")]
+    fn ref_mut_unfreeze_panics_when_saturated() {
+        let cell = CellBuilder::new(5)
+            .flag_limit(2)
+            .on_overflow(OverflowPolicy::Saturate)
+            .build();
+
+        let frozen = cell.borrow_mut().freeze();
+        let a = cell.borrow();
+        let _b = a.clone();
+        drop(a);
+
+        let _r = frozen.unfreeze();
+    }
+
+    #[cfg(not(feature = "single_thread"))]
+    #[test]
+    fn ref_mut_unfreeze_waits_for_other_readers() {
+        let cell = Cell::new(5);
+
+        let frozen = cell.borrow_mut().freeze();
+
+        let mut r = thread::scope(|s| {
+            let a = cell.borrow();
+
+            let reader = s.spawn(move || {
+                thread::sleep(Duration::from_millis(10));
+                drop(a);
+            });
+
+            let r = frozen.unfreeze();
+            assert_eq!(cell.flag.load(Ordering::SeqCst), usize::MAX);
+
+            reader.join().unwrap();
+            r
+        });
+
+        *r = 6;
+        drop(r);
+
+        assert_eq!(6, *cell.borrow());
+    }
+
     #[cfg(not(feature = "unsafe_debug"))]
     #[test]
     fn debug() {
@@ -523,4 +3354,10 @@ mod tests {
             format!("{:?}", Cell::new(B { value: 1 }))
         );
     }
+
+    #[derive(Debug)]
+    struct Pair {
+        a: u32,
+        b: String,
+    }
 }
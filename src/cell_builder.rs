@@ -0,0 +1,227 @@
+use crate::{cell_ref::REF_LIMIT_MAX, flag_limit::FlagLimit, Cell, OverflowPolicy};
+
+/// Builds a [`Cell`] with a custom limit on immutable borrows.
+///
+/// `Cell::new` allows up to `isize::MAX` immutable borrows, and
+/// [`CellRef::try_clone`] returns an error beyond that. A smaller limit
+/// detects leaked borrows earlier, and [`OverflowPolicy::Saturate`] avoids
+/// panicking entirely.
+///
+/// # Examples
+///
+/// ```rust
+/// use rt_ref::{CellBuilder, OverflowPolicy};
+///
+/// let cell = CellBuilder::new(1u32)
+///     .flag_limit(2)
+///     .on_overflow(OverflowPolicy::Error)
+///     .build();
+///
+/// let r_0 = cell.borrow();
+/// let r_1 = r_0.try_clone().unwrap();
+/// assert!(r_1.try_clone().is_err());
+/// ```
+///
+/// [`CellRef::try_clone`]: crate::CellRef::try_clone
+#[derive(Debug)]
+pub struct CellBuilder<T> {
+    inner: T,
+    flag_limit: usize,
+    on_overflow: OverflowPolicy,
+}
+
+impl<T> CellBuilder<T> {
+    /// Returns a new `CellBuilder` for a `Cell` holding `inner`.
+    pub fn new(inner: T) -> Self {
+        CellBuilder {
+            inner,
+            flag_limit: REF_LIMIT_MAX,
+            on_overflow: OverflowPolicy::default(),
+        }
+    }
+
+    /// Sets the maximum number of immutable borrows of the `Cell`.
+    ///
+    /// The limit is clamped to at least `1`, and at most `isize::MAX`, or
+    /// `isize::MAX - 1` when using [`OverflowPolicy::Saturate`].
+    ///
+    /// Once the limit is reached, [`Cell::try_borrow`] returns
+    /// [`BorrowFail::ReadLimitReached`] and [`Cell::borrow`] panics,
+    /// regardless of the [`OverflowPolicy`], which only applies to cloning a
+    /// `CellRef`.
+    ///
+    /// Defaults to `isize::MAX`. For a limit that is part of the `Cell`'s
+    /// type, use [`FixedCell`].
+    ///
+    /// [`BorrowFail::ReadLimitReached`]: crate::BorrowFail::ReadLimitReached
+    /// [`FixedCell`]: crate::FixedCell
+    pub fn flag_limit(mut self, flag_limit: usize) -> Self {
+        self.flag_limit = flag_limit;
+        self
+    }
+
+    /// Sets the behaviour when cloning a `CellRef` would exceed the flag
+    /// limit.
+    ///
+    /// Defaults to [`OverflowPolicy::Error`].
+    pub fn on_overflow(mut self, on_overflow: OverflowPolicy) -> Self {
+        self.on_overflow = on_overflow;
+        self
+    }
+
+    /// Returns the `Cell`.
+    pub fn build(self) -> Cell<T> {
+        let limit = FlagLimit::new(self.flag_limit, self.on_overflow);
+
+        Cell::new_with_limit(self.inner, limit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::Ordering;
+
+    use crate::{flag_limit::SATURATED, BorrowFail, CellRef, OverflowPolicy, RefOverflow};
+
+    use super::CellBuilder;
+
+    #[test]
+    fn build_defaults_to_error_at_isize_max() {
+        let cell = CellBuilder::new(1u32).build();
+
+        assert_eq!(isize::MAX as usize, cell.flag.limit.max);
+        assert_eq!(OverflowPolicy::Error, cell.flag.limit.policy);
+    }
+
+    #[test]
+    fn try_borrow_returns_read_limit_reached_at_limit() {
+        let cell = CellBuilder::new(1u32).flag_limit(2).build();
+
+        let _r_0 = cell.borrow();
+        let _r_1 = cell.borrow();
+
+        assert_eq!(
+            Err(BorrowFail::ReadLimitReached(2)),
+            cell.try_borrow().map(|_| ())
+        );
+        assert_eq!(2, cell.flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Expected to borrow `u32` immutably, but it was already borrowed (2 shared readers, which is the flag limit)."
+    )]
+    fn borrow_panics_at_limit() {
+        let cell = CellBuilder::new(1u32).flag_limit(2).build();
+
+        let _r_0 = cell.borrow();
+        let _r_1 = cell.borrow();
+        let _r_2 = cell.borrow();
+    }
+
+    #[test]
+    fn flag_limit_is_clamped_to_at_least_one() {
+        let cell = CellBuilder::new(1u32).flag_limit(0).build();
+
+        let r = cell.borrow();
+        assert_eq!(Err(RefOverflow::new::<u32>(1)), r.try_clone().map(|_| ()));
+    }
+
+    #[test]
+    fn error_policy_try_clone_returns_err_at_limit() {
+        let cell = CellBuilder::new(1u32)
+            .flag_limit(2)
+            .on_overflow(OverflowPolicy::Error)
+            .build();
+
+        let r_0 = cell.borrow();
+        let r_1 = r_0
+            .try_clone()
+            .expect("Expected clone below limit to succeed.");
+
+        assert_eq!(Err(RefOverflow::new::<u32>(2)), r_1.try_clone().map(|_| ()));
+        assert_eq!(2, cell.flag.load(Ordering::SeqCst));
+        assert!(cell.try_borrow().is_err());
+
+        drop((r_0, r_1));
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    #[should_panic(expected = "reached the flag limit (1).")]
+    fn error_policy_clone_panics_at_limit() {
+        let cell = CellBuilder::new(1u32)
+            .flag_limit(1)
+            .on_overflow(OverflowPolicy::Error)
+            .build();
+
+        let r = cell.borrow();
+        let _r_clone = CellRef::clone(&r);
+    }
+
+    #[test]
+    #[should_panic(expected = "Failed to clone `CellRef`: Ref count reached the flag limit (2).")]
+    fn panic_policy_try_clone_panics_at_limit() {
+        let cell = CellBuilder::new(1u32)
+            .flag_limit(2)
+            .on_overflow(OverflowPolicy::Panic)
+            .build();
+
+        let r_0 = cell.borrow();
+        let r_1 = r_0
+            .try_clone()
+            .expect("Expected clone below limit to succeed.");
+        let _r_2 = r_1.try_clone();
+    }
+
+    #[test]
+    #[should_panic(expected = "Failed to clone `CellRef`: Ref count reached the flag limit (1).")]
+    fn panic_policy_clone_panics_at_limit() {
+        let cell = CellBuilder::new(1u32)
+            .flag_limit(1)
+            .on_overflow(OverflowPolicy::Panic)
+            .build();
+
+        let r = cell.borrow();
+        let _r_clone = CellRef::clone(&r);
+    }
+
+    #[test]
+    fn saturate_policy_clone_succeeds_and_never_releases_borrow() {
+        let cell = CellBuilder::new(1u32)
+            .flag_limit(2)
+            .on_overflow(OverflowPolicy::Saturate)
+            .build();
+
+        let r_0 = cell.borrow();
+        let r_1 = r_0.clone();
+        assert_eq!(2, cell.flag.load(Ordering::SeqCst));
+
+        let r_2 = r_1.clone();
+        let r_3 = r_2
+            .try_clone()
+            .expect("Expected saturated clone to succeed.");
+        assert_eq!(SATURATED, cell.flag.load(Ordering::SeqCst));
+        assert_eq!(1, *r_3);
+
+        drop((r_0, r_1, r_2, r_3));
+        assert_eq!(SATURATED, cell.flag.load(Ordering::SeqCst));
+        assert!(cell.try_borrow().is_err());
+        assert!(cell.try_borrow_mut().is_err());
+    }
+
+    #[test]
+    fn saturate_policy_releases_borrows_below_limit() {
+        let cell = CellBuilder::new(1u32)
+            .flag_limit(3)
+            .on_overflow(OverflowPolicy::Saturate)
+            .build();
+
+        let r_0 = cell.borrow();
+        let r_1 = r_0.clone();
+        drop((r_0, r_1));
+
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+        assert!(cell.try_borrow_mut().is_ok());
+    }
+}
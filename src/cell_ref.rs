@@ -1,20 +1,31 @@
 use std::{
-    mem,
+    any::Any,
+    borrow::{Borrow, Cow},
+    collections::HashMap,
+    fmt,
+    hash::{BuildHasher, Hash},
+    mem::{self, ManuallyDrop},
     ops::Deref,
-    sync::atomic::{AtomicUsize, Ordering},
+    pin::Pin,
+    ptr,
+    sync::{atomic::Ordering, Arc},
 };
 
-use crate::RefOverflow;
+use crate::{
+    flag::{release_reader, Flag},
+    flag_limit::SATURATED,
+    BorrowFail, BorrowToken, Cell, CellRefMut, OverflowPolicy, OwnedCellRef, RawBorrow, Ref,
+    RefOverflow, WeakCellRef,
+};
 
 /// An immutable reference to data in a `Cell`.
 ///
 /// Access the value via `std::ops::Deref` (e.g. `*val`)
-#[derive(Debug)]
 pub struct CellRef<'a, T>
 where
     T: ?Sized + 'a,
 {
-    pub(crate) flag: &'a AtomicUsize,
+    pub(crate) flag: &'a Flag,
     pub(crate) value: &'a T,
 }
 
@@ -36,20 +47,80 @@ where
     /// * Applications that hold `CellRef`s with an ever-increasing reference
     ///   count are not supported by this library.
     ///
-    ///     Reaching `isize::MAX` may be possible with
-    ///     `std::mem::forget(CellRef::clone(&r))`.
+    ///   Reaching `isize::MAX` may be possible with
+    ///   `std::mem::forget(CellRef::clone(&r))`.
+    ///
+    /// For `Cell`s built with a smaller flag limit, reaching the limit is
+    /// handled by the `Cell`'s [`OverflowPolicy`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the flag limit is reached and the policy is
+    /// [`OverflowPolicy::Panic`].
     pub fn try_clone(&self) -> Result<Self, RefOverflow> {
-        let previous_value = self.flag.fetch_add(1, Ordering::Relaxed);
-
-        let overflow = previous_value >= REF_LIMIT_MAX;
-        if unlikely(overflow) {
-            self.flag.fetch_sub(1, Ordering::Relaxed);
-            Err(RefOverflow)
-        } else {
-            Ok(CellRef {
-                flag: self.flag,
-                value: self.value,
-            })
+        // Check the limit before incrementing, so that the flag never exceeds
+        // the limit, even transiently.
+        let mut val = self.flag.load(Ordering::Relaxed);
+        loop {
+            let overflow = val >= self.flag.limit.max;
+            if unlikely(overflow) {
+                match self.flag.limit.policy {
+                    OverflowPolicy::Panic => panic!(
+                        "Failed to clone `CellRef`: Ref count reached the flag limit ({}).",
+                        self.flag.limit.max
+                    ),
+                    OverflowPolicy::Error => {
+                        return Err(RefOverflow::new::<T>(self.flag.limit.max))
+                    }
+                    OverflowPolicy::Saturate => {
+                        if val == SATURATED {
+                            return Ok(self.copy_saturated());
+                        }
+
+                        match self.flag.compare_exchange_weak(
+                            val,
+                            SATURATED,
+                            Ordering::Relaxed,
+                            Ordering::Relaxed,
+                        ) {
+                            Ok(_) => return Ok(self.copy_saturated()),
+                            Err(current) => {
+                                val = current;
+                                continue;
+                            }
+                        }
+                    }
+                }
+            }
+
+            match self.flag.compare_exchange_weak(
+                val,
+                val + 1,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => {
+                    #[cfg(feature = "leak_guard")]
+                    self.flag.record_readers(val + 1);
+
+                    return Ok(CellRef {
+                        flag: self.flag,
+                        value: self.value,
+                    });
+                }
+                Err(current) => val = current,
+            }
+        }
+    }
+
+    /// Returns a copy of this `CellRef` without incrementing the flag.
+    ///
+    /// Only valid once the borrow is saturated, as saturated borrows are never
+    /// released.
+    fn copy_saturated(&self) -> Self {
+        CellRef {
+            flag: self.flag,
+            value: self.value,
         }
     }
 
@@ -58,9 +129,11 @@ where
     ///
     /// The `Cell` is already immutably borrowed, so this cannot fail.
     ///
-    /// This is an associated function that needs to be used as
-    /// `CellRef::map(...)`. A method would interfere with methods of the
-    /// same name on the contents of a `CellRef` used through `Deref`.
+    /// Prefer calling this as `CellRef::map(...)`: as a method, it takes
+    /// precedence over methods of the same name on the contents of a `CellRef`
+    /// used through `Deref`, such as `Option::map`. [`cell_ref_map`] may be
+    /// called as a method without this ambiguity.
+    ///
     /// Further this preserves the borrow of the value and hence does the
     /// proper cleanup when it's dropped.
     ///
@@ -96,145 +169,1405 @@ where
     /// let b2: CellRef<'_, u32> = CellRef::map(b1, |t| &t.0);
     /// assert_eq!(*b2, 5);
     /// ```
+    ///
+    /// [`cell_ref_map`]: Self::cell_ref_map
     pub fn map<U, F>(self, f: F) -> CellRef<'a, U>
     where
         F: FnOnce(&T) -> &U,
         U: ?Sized,
     {
-        let flag = unsafe { &*(self.flag as *const _) };
-        let value = unsafe { &*(self.value as *const _) };
+        let value = f(self.value);
 
-        mem::forget(self);
+        // The borrow is transferred to the returned `CellRef`, so `self` must
+        // not release it. The fields are shared references, so they are
+        // copied out rather than reborrowed through a raw pointer.
+        let this = ManuallyDrop::new(self);
 
         CellRef {
-            flag,
-            value: f(value),
+            flag: this.flag,
+            value,
         }
     }
-}
-
-impl<'a, T> Deref for CellRef<'a, T>
-where
-    T: ?Sized,
-{
-    type Target = T;
 
-    fn deref(&self) -> &T {
-        self.value
+    /// Makes a new `CellRef` for a component of the borrowed data, passing
+    /// `ctx` to the projection, which preserves the existing borrow.
+    ///
+    /// This is the same as [`map`], for projections that need auxiliary data,
+    /// such as an index computed elsewhere, without capturing it in the
+    /// closure.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_ref::{Cell, CellRef};
+    ///
+    /// let c = Cell::new(vec![5, 6, 7]);
+    /// let index = 1;
+    ///
+    /// let n: CellRef<'_, u32> = c.borrow().map_with(index, |v, i| &v[i]);
+    /// assert_eq!(*n, 6);
+    /// ```
+    ///
+    /// [`map`]: Self::map
+    pub fn map_with<C, U, F>(self, ctx: C, f: F) -> CellRef<'a, U>
+    where
+        F: FnOnce(&T, C) -> &U,
+        U: ?Sized,
+    {
+        self.map(|value| f(value, ctx))
     }
-}
 
-impl<'a, T> Drop for CellRef<'a, T>
-where
-    T: ?Sized,
-{
-    fn drop(&mut self) {
-        self.flag.fetch_sub(1, Ordering::Release);
+    /// Makes a new `CellRef` for a component of the borrowed data which
+    /// preserves the existing borrow.
+    ///
+    /// This is the same as [`map`], with a name that does not collide with
+    /// methods on the contents of the `CellRef`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_ref::{Cell, CellRef};
+    ///
+    /// let c = Cell::new(Some(5));
+    /// let b1: CellRef<'_, Option<u32>> = c.borrow();
+    ///
+    /// // `Option::map`, through `Deref`.
+    /// assert_eq!(Some(6), (*b1).map(|n| n + 1));
+    ///
+    /// let b2: CellRef<'_, u32> = b1.cell_ref_map(|n| n.as_ref().unwrap());
+    /// assert_eq!(5, *b2);
+    /// ```
+    ///
+    /// [`map`]: Self::map
+    pub fn cell_ref_map<U, F>(self, f: F) -> CellRef<'a, U>
+    where
+        F: FnOnce(&T) -> &U,
+        U: ?Sized,
+    {
+        self.map(f)
     }
-}
 
-impl<'a, T> Clone for CellRef<'a, T>
-where
-    T: ?Sized,
-{
-    /// Returns a clone of this `CellRef`.
+    /// Makes a new `CellRef` for an optional component of the borrowed data.
     ///
-    /// # Panics
+    /// If `f` returns `Some`, the existing borrow is transferred to the
+    /// returned `CellRef`, like [`map`]. If `f` returns `None`, this
+    /// `CellRef` is dropped and the borrow is released.
     ///
-    /// Panics if the number of references is `isize::MAX`:
+    /// Unlike a `filter_map` style projection, the original `CellRef` is not
+    /// returned on failure, so the `Cell` may be mutably borrowed as soon as
+    /// this returns `None`.
     ///
-    /// * Having 2 billion / 9 quintillion references to an object is not a
-    ///   realistic scenario in most applications.
-    /// * Applications that hold `CellRef`s with an ever-increasing reference
-    ///   count are not supported by this library.
+    /// # Examples
     ///
-    ///     Reaching `isize::MAX` may be possible with
-    ///     `std::mem::forget(CellRef::clone(&r))`.
-    fn clone(&self) -> Self {
-        self.try_clone()
-            .unwrap_or_else(|e| panic!("Failed to clone `CellRef`: {e}"))
+    /// ```rust
+    /// use rt_ref::{Cell, CellRef};
+    ///
+    /// let c = Cell::new(vec![1, 2, 3]);
+    ///
+    /// let second: Option<CellRef<'_, u32>> = c.borrow().and_then(|v| v.get(1));
+    /// assert_eq!(Some(2), second.map(|second| *second));
+    ///
+    /// let fourth: Option<CellRef<'_, u32>> = c.borrow().and_then(|v| v.get(3));
+    /// assert!(fourth.is_none());
+    /// assert!(c.try_borrow_mut().is_ok());
+    /// ```
+    ///
+    /// [`map`]: Self::map
+    pub fn and_then<U, F>(self, f: F) -> Option<CellRef<'a, U>>
+    where
+        F: FnOnce(&T) -> Option<&U>,
+        U: ?Sized,
+    {
+        let value = f(self.value)?;
+        let this = ManuallyDrop::new(self);
+
+        Some(CellRef {
+            flag: this.flag,
+            value,
+        })
     }
-}
 
-/// Trick to mimic `std::intrinsics::unlikely` on stable Rust.
-#[cold]
-#[inline(always)]
-fn cold() {}
+    /// Makes a new `CellRef` for an optional component of the borrowed data,
+    /// or returns this `CellRef` if `f` returns `None`.
+    fn filter_map<U, F>(self, f: F) -> Result<CellRef<'a, U>, Self>
+    where
+        F: FnOnce(&'a T) -> Option<&'a U>,
+        U: ?Sized,
+    {
+        let value: &'a T = self.value;
 
-#[inline(always)]
-fn unlikely(cond: bool) -> bool {
-    if cond {
-        cold();
+        match f(value) {
+            Some(value) => {
+                let flag = self.flag;
+
+                mem::forget(self);
+
+                Ok(CellRef { flag, value })
+            }
+            None => Err(self),
+        }
     }
-    cond
-}
 
-#[cfg(test)]
-mod tests {
-    use std::{
-        error::Error,
-        sync::atomic::{AtomicUsize, Ordering},
-    };
+    /// Returns an owned copy of the borrowed value as a `Cow`, releasing the
+    /// borrow.
+    ///
+    /// A `Cow::Borrowed` cannot be returned without keeping the `Cell`
+    /// borrowed for `'a`. If that is desired, use [`leak`] instead, e.g.
+    /// `Cow::Borrowed(cell_ref.leak())`.
+    ///
+    /// [`leak`]: Self::leak
+    pub fn into_cow(self) -> Cow<'a, T>
+    where
+        T: ToOwned,
+    {
+        Cow::Owned(self.value.to_owned())
+    }
 
-    use crate::RefOverflow;
+    /// Returns a `Copy` value read from the borrowed data, releasing the
+    /// borrow.
+    ///
+    /// This avoids holding a `CellRef` to a field when only its value is
+    /// needed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_ref::Cell;
+    ///
+    /// let c = Cell::new((5, 'b'));
+    ///
+    /// let n: u32 = c.borrow().map_copy(|t| t.0);
+    /// assert_eq!(5, n);
+    /// assert!(c.try_borrow_mut().is_ok());
+    /// ```
+    pub fn map_copy<U, F>(self, f: F) -> U
+    where
+        F: FnOnce(&T) -> U,
+        U: Copy,
+    {
+        f(self.value)
+    }
 
-    use super::{CellRef, REF_LIMIT_MAX};
+    /// Returns a value computed from the borrowed data, releasing the borrow.
+    ///
+    /// Unlike [`map_copy`], the returned value may be any type, such as an
+    /// owned value derived from the borrowed data.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_ref::Cell;
+    ///
+    /// let c = Cell::new(vec![1, 2]);
+    ///
+    /// let s = c.borrow().read(|v| format!("{v:?}"));
+    /// assert_eq!("[1, 2]", s);
+    /// assert!(c.try_borrow_mut().is_ok());
+    /// ```
+    ///
+    /// [`map_copy`]: Self::map_copy
+    pub fn read<R, F>(self, f: F) -> R
+    where
+        F: FnOnce(&T) -> R,
+    {
+        f(self.value)
+    }
 
-    #[test]
-    fn try_clone_returns_ok_when_ref_count_less_than_isize_max() {
-        let flag = &AtomicUsize::new(1);
-        let value = &1u32;
-        let cell_ref = CellRef { flag, value };
+    /// Converts this `CellRef` into an [`OwnedCellRef`] that keeps `cell`
+    /// alive, transferring the borrow.
+    ///
+    /// This allows a borrow to be held in `'static` contexts, e.g. moved into
+    /// a spawned task.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this `CellRef` does not borrow the whole value of `cell`,
+    /// e.g. if it was borrowed from another `Cell`, or mapped to a component
+    /// of the value.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::sync::Arc;
+    ///
+    /// use rt_ref::{Cell, OwnedCellRef};
+    ///
+    /// let cell = Arc::new(Cell::new(1));
+    ///
+    /// let owned: OwnedCellRef<u32> = cell.borrow().into_owned(Arc::clone(&cell));
+    /// drop(cell);
+    ///
+    /// assert_eq!(1, *owned);
+    /// ```
+    pub fn into_owned(self, cell: Arc<Cell<T>>) -> OwnedCellRef<T> {
+        assert!(
+            ptr::eq(self.flag, &cell.flag) && ptr::eq(self.value, cell.inner.get()),
+            "Expected `CellRef` to borrow the whole value of the given `Cell`."
+        );
 
-        assert_eq!(1, cell_ref.flag.load(Ordering::SeqCst));
+        mem::forget(self);
 
-        let try_clone_result = cell_ref.try_clone();
+        OwnedCellRef { cell }
+    }
 
-        let cloned = try_clone_result.expect("try_clone_result to be ok");
-        assert_eq!(2, cloned.flag.load(Ordering::SeqCst));
+    /// Returns the reference to the borrowed value, leaking the borrow.
+    ///
+    /// The `Cell` remains immutably borrowed for the rest of its lifetime, so
+    /// it can never be borrowed mutably again.
+    pub fn leak(self) -> &'a T {
+        let value = self.value;
+
+        mem::forget(self);
+
+        value
     }
 
-    #[test]
-    fn try_clone_returns_err_when_ref_count_equals_isize_max() {
-        let flag = &AtomicUsize::new(REF_LIMIT_MAX);
-        let value = &1u32;
-        let cell_ref = CellRef { flag, value };
+    /// Separates this `CellRef` into its borrow and the reference to the
+    /// borrowed value, without releasing the borrow.
+    ///
+    /// This is intended for building other guards on top of `CellRef`. The
+    /// `Cell` remains immutably borrowed until the parts are passed to
+    /// [`from_raw_parts`] and the reconstructed `CellRef` is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_ref::{Cell, CellRef};
+    ///
+    /// let c = Cell::new((1, 'a'));
+    ///
+    /// let (raw_borrow, value) = c.borrow().into_raw_parts();
+    /// assert!(c.try_borrow_mut().is_err());
+    ///
+    /// let n = unsafe { CellRef::from_raw_parts(raw_borrow, &value.0) };
+    /// assert_eq!(1, *n);
+    ///
+    /// drop(n);
+    /// assert!(c.try_borrow_mut().is_ok());
+    /// ```
+    ///
+    /// [`from_raw_parts`]: Self::from_raw_parts
+    pub fn into_raw_parts(self) -> (RawBorrow<'a>, &'a T) {
+        let this = ManuallyDrop::new(self);
 
-        assert_eq!(REF_LIMIT_MAX, cell_ref.flag.load(Ordering::SeqCst));
+        let raw_borrow = RawBorrow {
+            flag: this.flag,
+            shared: false,
+        };
 
-        let try_clone_result = cell_ref.try_clone();
+        (raw_borrow, this.value)
+    }
 
-        let e = try_clone_result.expect_err("try_clone_result to be err");
-        assert_eq!(RefOverflow, e);
-        assert!(e.source().is_none());
+    /// Reconstructs a `CellRef` from parts returned by [`into_raw_parts`].
+    ///
+    /// # Safety
+    ///
+    /// * `raw_borrow` must have been returned by [`CellRef::into_raw_parts`],
+    ///   not by `CellRefMut::into_raw_parts`.
+    /// * `value` must be the reference returned alongside `raw_borrow`, or
+    ///   derived from it, e.g. a reference to one of its fields.
+    ///
+    /// The borrow is released when the returned `CellRef` is dropped.
+    ///
+    /// [`into_raw_parts`]: Self::into_raw_parts
+    pub unsafe fn from_raw_parts(raw_borrow: RawBorrow<'a>, value: &'a T) -> Self {
+        CellRef {
+            flag: raw_borrow.flag,
+            value,
+        }
+    }
 
-        // Ensure that the overflow is not persisted
-        assert_eq!(REF_LIMIT_MAX, cell_ref.flag.load(Ordering::SeqCst));
+    /// Parks this borrow in a [`BorrowToken`], which can be passed to
+    /// [`Cell::reuse`] to get a `CellRef` without borrowing the `Cell` again.
+    ///
+    /// The `Cell` remains immutably borrowed until the token is dropped. See
+    /// [`BorrowToken`] for the reuse protocol.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_ref::Cell;
+    ///
+    /// let c = Cell::new(1);
+    ///
+    /// let mut token = c.borrow().park();
+    /// for _ in 0..3 {
+    ///     let r = c.reuse(token);
+    ///     assert_eq!(1, *r);
+    ///     token = r.park();
+    /// }
+    ///
+    /// assert!(c.try_borrow_mut().is_err());
+    /// drop(token);
+    /// assert!(c.try_borrow_mut().is_ok());
+    /// ```
+    pub fn park(self) -> BorrowToken<'a> {
+        let (raw_borrow, _) = self.into_raw_parts();
+
+        BorrowToken {
+            flag: raw_borrow.flag,
+        }
     }
 
-    #[test]
-    fn clone_returns_cell_ref_when_ref_count_less_than_isize_max() {
-        let flag = &AtomicUsize::new(1);
-        let value = &1u32;
-        let cell_ref = CellRef { flag, value };
+    /// Releases this borrow, and returns a [`WeakCellRef`] to `cell`, which
+    /// can be upgraded to a fresh borrow later.
+    ///
+    /// `cell` may be of a different type to `T`, if this `CellRef` was mapped
+    /// to a component of its value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this `CellRef` does not borrow from `cell`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_ref::Cell;
+    ///
+    /// let c = Cell::new(1);
+    ///
+    /// let weak = c.borrow().downgrade_to_weak(&c);
+    /// *c.borrow_mut() += 1;
+    ///
+    /// assert_eq!(Ok(2), weak.upgrade().map(|value| *value));
+    /// ```
+    pub fn downgrade_to_weak<C>(self, cell: &'a Cell<C>) -> WeakCellRef<'a, C> {
+        assert!(
+            ptr::eq(self.flag, &cell.flag),
+            "Expected `CellRef` to borrow from the given `Cell`."
+        );
 
-        assert_eq!(1, cell_ref.flag.load(Ordering::SeqCst));
+        WeakCellRef::new(cell)
+    }
 
-        let cloned = cell_ref.clone();
+    /// Returns whether this is the only outstanding `CellRef` to the value.
+    ///
+    /// The result may be stale as soon as it is returned: another thread may
+    /// clone a `CellRef` sharing this borrow immediately afterwards. It is
+    /// reliable only when no other thread has access to a `CellRef` sharing
+    /// this borrow, or to the `Cell`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_ref::Cell;
+    ///
+    /// let c = Cell::new(1);
+    ///
+    /// let r_0 = c.borrow();
+    /// assert!(r_0.is_last());
+    ///
+    /// let r_1 = r_0.clone();
+    /// assert!(!r_0.is_last());
+    ///
+    /// drop(r_1);
+    /// assert!(r_0.is_last());
+    /// ```
+    pub fn is_last(&self) -> bool {
+        self.flag.load(Ordering::Acquire) == 1
+    }
 
-        assert_eq!(2, cell_ref.flag.load(Ordering::SeqCst));
-        assert_eq!(2, cloned.flag.load(Ordering::SeqCst));
+    /// Returns the address of the borrowed value.
+    ///
+    /// This is intended for identity checks and debugging. [`map`] only
+    /// narrows the reference, so the mapped `CellRef` points into the original
+    /// value rather than a copy of it.
+    ///
+    /// [`map`]: Self::map
+    pub fn addr(&self) -> usize {
+        self.value as *const T as *const () as usize
     }
 
-    #[test]
-    #[should_panic(expected = "Failed to clone `CellRef`: Ref count exceeded `isize::MAX`")]
-    fn clone_panics_when_ref_count_equals_isize_max() {
-        let flag = &AtomicUsize::new(REF_LIMIT_MAX);
-        let value = &1u32;
-        let cell_ref = CellRef { flag, value };
+    /// Returns a pinned reference to the borrowed value.
+    ///
+    /// # Safety
+    ///
+    /// The caller must uphold the [pinning guarantee]: once this is called, the
+    /// value must not be moved out of the `Cell` until it is dropped, e.g. by
+    /// `Cell::into_inner` or through a later mutable borrow.
+    ///
+    /// [pinning guarantee]: std::pin#what-is-pinning
+    pub unsafe fn as_pin(&self) -> Pin<&T> {
+        Pin::new_unchecked(self.value)
+    }
+}
 
-        assert_eq!(REF_LIMIT_MAX, cell_ref.flag.load(Ordering::SeqCst));
+impl<'a, T> CellRef<'a, T> {
+    /// Converts this `CellRef` into a [`Ref`], preserving the existing borrow.
+    pub fn into_ref(self) -> Ref<'a, T> {
+        Ref::new(self)
+    }
+}
 
-        let _clone = cell_ref.clone();
+impl<'a> CellRef<'a, dyn Any> {
+    /// Returns a reference to the borrowed value if it is of type `U`, or
+    /// `None` if it isn't.
+    pub fn downcast_ref<U>(&self) -> Option<&U>
+    where
+        U: Any,
+    {
+        self.value.downcast_ref::<U>()
+    }
+
+    /// Converts this `CellRef` into a `CellRef<U>` if the borrowed value is of
+    /// type `U`, preserving the existing borrow.
+    ///
+    /// If the value is of another type, this `CellRef` is returned unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_ref::{Cell, CellRef};
+    ///
+    /// let c = Cell::new(1u32);
+    ///
+    /// let value = c.borrow_any().downcast::<i32>().unwrap_err();
+    /// let value: CellRef<'_, u32> = value.downcast::<u32>().unwrap();
+    /// assert_eq!(1, *value);
+    /// ```
+    pub fn downcast<U>(self) -> Result<CellRef<'a, U>, Self>
+    where
+        U: Any,
+    {
+        self.filter_map(|value| value.downcast_ref::<U>())
+    }
+}
+
+impl<'a, K, V, S> CellRef<'a, HashMap<K, V, S>>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+{
+    /// Makes a new `CellRef` for the value of `key` in the borrowed map, or
+    /// returns the original `CellRef` if the key is absent.
+    ///
+    /// This is an associated function that needs to be used as
+    /// `CellRef::get(...)`, so that it does not interfere with `HashMap::get`
+    /// used through `Deref`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    ///
+    /// use rt_ref::{Cell, CellRef};
+    ///
+    /// let c = Cell::new(HashMap::from([("a", 1)]));
+    ///
+    /// let map = CellRef::get(c.borrow(), "b").unwrap_err();
+    /// let a: CellRef<'_, u32> = CellRef::get(map, "a").unwrap();
+    /// assert_eq!(1, *a);
+    /// ```
+    pub fn get<Q>(this: Self, key: &Q) -> Result<CellRef<'a, V>, Self>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        this.filter_map(|map| map.get(key))
+    }
+}
+
+impl<'a, T> From<CellRefMut<'a, T>> for CellRef<'a, T>
+where
+    T: ?Sized,
+{
+    /// Downgrades the mutable borrow to an immutable borrow, so that the `Cell`
+    /// may be borrowed immutably elsewhere.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `CellRefMut` shares its borrow with another `CellRefMut`,
+    /// e.g. after `CellRefMut::split_first_mut`.
+    fn from(cell_ref_mut: CellRefMut<'a, T>) -> Self {
+        if cell_ref_mut
+            .flag
+            .compare_exchange(usize::MAX, 1, Ordering::AcqRel, Ordering::Acquire)
+            .is_err()
+        {
+            panic!(
+                "Cannot downgrade a `CellRefMut` that shares its borrow with another `CellRefMut`."
+            );
+        }
+
+        #[cfg(feature = "track_borrows")]
+        cell_ref_mut.flag.clear_writer();
+
+        // Reborrowing `value` and then moving `cell_ref_mut` into
+        // `mem::forget` would invalidate the reborrow, so the `&mut T` is read
+        // out of a `ManuallyDrop` instead.
+        let cell_ref_mut = ManuallyDrop::new(cell_ref_mut);
+        let value: &'a mut T = unsafe { ptr::read(&cell_ref_mut.value) };
+
+        CellRef {
+            flag: cell_ref_mut.flag,
+            value,
+        }
+    }
+}
+
+impl<'a, T, const N: usize> From<CellRef<'a, [T; N]>> for CellRef<'a, [T]> {
+    /// Coerces the borrowed array to a slice, preserving the existing borrow.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_ref::{Cell, CellRef};
+    ///
+    /// let c = Cell::new([1, 2, 3]);
+    ///
+    /// let slice: CellRef<'_, [u32]> = c.borrow().into();
+    /// assert_eq!(&[2, 3], &slice[1..]);
+    /// ```
+    fn from(cell_ref: CellRef<'a, [T; N]>) -> Self {
+        cell_ref.map(|array| array as &[T])
+    }
+}
+
+impl<'a, T> TryFrom<&'a Cell<T>> for CellRef<'a, T>
+where
+    T: ?Sized,
+{
+    type Error = BorrowFail;
+
+    /// Borrows the `Cell` immutably, like [`Cell::try_borrow`].
+    fn try_from(cell: &'a Cell<T>) -> Result<Self, BorrowFail> {
+        cell.try_borrow()
+    }
+}
+
+impl<'a, 'b, T> PartialEq<CellRefMut<'b, T>> for CellRef<'a, T>
+where
+    T: PartialEq + ?Sized,
+{
+    fn eq(&self, other: &CellRefMut<'b, T>) -> bool {
+        let r_self: &T = self;
+        let r_other: &T = other;
+        r_self == r_other
+    }
+}
+
+#[cfg(not(feature = "unsafe_debug"))]
+impl<'a, T> fmt::Debug for CellRef<'a, T>
+where
+    T: ?Sized,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CellRef")
+            .field("readers", &self.flag.load(Ordering::Relaxed))
+            .finish()
+    }
+}
+
+#[cfg(feature = "unsafe_debug")]
+impl<'a, T> fmt::Debug for CellRef<'a, T>
+where
+    T: ?Sized + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CellRef")
+            .field("readers", &self.flag.load(Ordering::Relaxed))
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+impl<'a, T> Deref for CellRef<'a, T>
+where
+    T: ?Sized,
+{
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.value
+    }
+}
+
+impl<'a, T> Drop for CellRef<'a, T>
+where
+    T: ?Sized,
+{
+    fn drop(&mut self) {
+        release_reader(self.flag);
+    }
+}
+
+impl<'a, T> Clone for CellRef<'a, T>
+where
+    T: ?Sized,
+{
+    /// Returns a clone of this `CellRef`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of references has reached the flag limit, which
+    /// is `isize::MAX` unless set through [`CellBuilder`]:
+    ///
+    /// * Having 2 billion / 9 quintillion references to an object is not a
+    ///   realistic scenario in most applications.
+    /// * Applications that hold `CellRef`s with an ever-increasing reference
+    ///   count are not supported by this library.
+    ///
+    ///   Reaching `isize::MAX` may be possible with
+    ///   `std::mem::forget(CellRef::clone(&r))`.
+    ///
+    /// With the `"abort_on_overflow"` feature, the process is aborted instead.
+    /// Reaching `isize::MAX` implies a serious leak, and a panic may be caught
+    /// and execution continued with corrupted borrow state. Reaching a smaller
+    /// flag limit set through [`CellBuilder`] still panics.
+    ///
+    /// [`CellBuilder`]: crate::CellBuilder
+    fn clone(&self) -> Self {
+        self.try_clone().unwrap_or_else(|e| {
+            #[cfg(feature = "abort_on_overflow")]
+            if self.flag.limit.max == REF_LIMIT_MAX {
+                eprintln!("Failed to clone `CellRef`: {e}");
+                std::process::abort();
+            }
+
+            panic!("Failed to clone `CellRef`: {e}")
+        })
+    }
+}
+
+/// Trick to mimic `std::intrinsics::unlikely` on stable Rust.
+#[cold]
+#[inline(always)]
+fn cold() {}
+
+#[inline(always)]
+fn unlikely(cond: bool) -> bool {
+    if cond {
+        cold();
+    }
+    cond
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        borrow::Cow,
+        collections::HashMap,
+        error::Error,
+        marker::PhantomPinned,
+        panic::{self, AssertUnwindSafe},
+        pin::Pin,
+        sync::{atomic::Ordering, Arc},
+    };
+
+    #[cfg(not(feature = "single_thread"))]
+    use std::{sync::atomic::AtomicBool, thread};
+
+    use crate::{flag::Flag, flag_limit::FlagLimit, BorrowFail, Cell, OverflowPolicy, RefOverflow};
+
+    use super::{CellRef, REF_LIMIT_MAX};
+
+    // Also run under Miri, as `map` transfers the borrow out of a forgotten
+    // `CellRef`: `cargo +nightly miri test --lib cell_ref::tests::map`
+    #[test]
+    fn map_transfers_borrow_to_mapped_ref() {
+        let cell = Cell::new((1u32, String::from("a")));
+
+        let cell_ref = cell.borrow();
+        let cell_ref_clone = cell_ref.clone();
+        let s = cell_ref_clone.map(|pair| pair.1.as_str());
+        assert_eq!(2, cell.flag.load(Ordering::SeqCst));
+
+        drop(cell_ref);
+        assert_eq!("a", &*s);
+        assert_eq!(1, cell.flag.load(Ordering::SeqCst));
+
+        let n = cell.borrow().map(|pair| &pair.0);
+        drop(s);
+        assert_eq!(1, *n);
+
+        drop(n);
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+        cell.borrow_mut().1.push('b');
+        assert_eq!("ab", &*cell.borrow().map(|pair| pair.1.as_str()));
+    }
+
+    #[test]
+    fn map_with_projects_with_index_context() {
+        let cell: Box<Cell<[u32]>> = Cell::from_boxed_slice(Box::new([1, 2, 3]));
+
+        let cell_ref = cell.borrow();
+        let cell_ref_clone = cell_ref.clone();
+        let second = cell_ref_clone.map_with(1, |values, i| &values[i]);
+        let tail = cell_ref.map_with(1.., |values, range| &values[range]);
+        assert_eq!(2, *second);
+        assert_eq!(&[2, 3], &*tail);
+        assert_eq!(2, cell.flag.load(Ordering::SeqCst));
+
+        drop(second);
+        assert_eq!(1, cell.flag.load(Ordering::SeqCst));
+
+        drop(tail);
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn map_with_releases_borrow_when_f_panics() {
+        let cell = Cell::new(vec![1u32]);
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            cell.borrow().map_with(1, |values, i| &values[i])
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn cell_ref_map_does_not_collide_with_option_map() {
+        let cell = Cell::new(Some(5u32));
+
+        let cell_ref = cell.borrow();
+        assert_eq!(Some(6), (*cell_ref).map(|n| n + 1));
+
+        let n = cell_ref.cell_ref_map(|n| n.as_ref().expect("Expected `Some`."));
+        assert_eq!(5, *n);
+        assert_eq!(1, cell.flag.load(Ordering::SeqCst));
+
+        drop(n);
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn map_releases_borrow_when_f_panics() {
+        let cell = Cell::new(1u32);
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            cell.borrow().map(|_: &u32| -> &u32 { panic!("map") })
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn and_then_transfers_borrow_when_some() {
+        let cell = Cell::new(vec![1u32, 2]);
+
+        let second = cell
+            .borrow()
+            .and_then(|v| v.get(1))
+            .expect("Expected second element to exist.");
+        assert_eq!(2, *second);
+        assert_eq!(1, cell.flag.load(Ordering::SeqCst));
+
+        drop(second);
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn raw_parts_round_trip_keeps_flag_balanced() {
+        let cell = Cell::new((1u32, 'a'));
+
+        let cell_ref = cell.borrow();
+        let cell_ref_clone = cell_ref.clone();
+        let (raw_borrow, value) = cell_ref.into_raw_parts();
+        assert_eq!(2, cell.flag.load(Ordering::SeqCst));
+
+        let n = unsafe { CellRef::from_raw_parts(raw_borrow, &value.0) };
+        assert_eq!(1, *n);
+        assert_eq!(2, cell.flag.load(Ordering::SeqCst));
+
+        drop(cell_ref_clone);
+        drop(n);
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn partial_eq_cell_ref_mut_compares_value() {
+        let cell = Cell::new(1u32);
+        let cell_equal = Cell::new(1u32);
+        let cell_unequal = Cell::new(2u32);
+
+        let cell_ref = cell.borrow();
+
+        assert!(cell_ref == cell_equal.borrow_mut());
+        assert!(cell_ref != cell_unequal.borrow_mut());
+    }
+
+    #[cfg(not(feature = "unsafe_debug"))]
+    #[test]
+    fn debug_includes_reader_count() {
+        let cell = Cell::new(1u32);
+
+        let r_0 = cell.borrow();
+        assert_eq!("CellRef { readers: 1 }", format!("{r_0:?}"));
+
+        let r_1 = r_0.clone();
+        assert_eq!("CellRef { readers: 2 }", format!("{r_0:?}"));
+        assert_eq!("CellRef { readers: 2 }", format!("{r_1:?}"));
+    }
+
+    #[cfg(feature = "unsafe_debug")]
+    #[test]
+    fn debug_includes_reader_count_and_value() {
+        let cell = Cell::new(1u32);
+
+        let r_0 = cell.borrow();
+        assert_eq!("CellRef { readers: 1, value: 1 }", format!("{r_0:?}"));
+
+        let _r_1 = r_0.clone();
+        assert_eq!("CellRef { readers: 2, value: 1 }", format!("{r_0:?}"));
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(
+        expected = "Expected `CellRef` to hold an immutable borrow when dropped, but the borrow flag was 0."
+    )]
+    fn drop_panics_when_flag_is_not_borrowed() {
+        let flag = Flag::new(0);
+
+        drop(CellRef {
+            flag: &flag,
+            value: &1,
+        });
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "Expected `CellRef` to hold an immutable borrow when dropped")]
+    fn drop_panics_when_flag_is_borrowed_mutably() {
+        let cell = Cell::new(1u32);
+
+        let cell_ref_mut = cell.borrow_mut();
+        // Deliberately mis-accounted: a `CellRef` sharing a mutable borrow.
+        let cell_ref = CellRef {
+            flag: cell_ref_mut.flag,
+            value: &1,
+        };
+
+        drop(cell_ref);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(
+        expected = "Expected `CellRef` to hold an immutable borrow when dropped, but the borrow flag was 0."
+    )]
+    fn drop_panics_when_saturating_flag_is_not_borrowed() {
+        let flag = Flag::with_limit(0, FlagLimit::new(2, OverflowPolicy::Saturate));
+
+        drop(CellRef {
+            flag: &flag,
+            value: &1,
+        });
+    }
+
+    #[test]
+    fn cell_ref_is_two_pointers() {
+        assert_eq!(
+            2 * std::mem::size_of::<usize>(),
+            std::mem::size_of::<CellRef<'_, u32>>()
+        );
+    }
+
+    #[test]
+    fn addr_returns_address_of_borrowed_value() {
+        let cell = Cell::new(1u32);
+
+        let cell_ref = cell.borrow();
+
+        assert_eq!(cell.inner.get() as usize, cell_ref.addr());
+    }
+
+    #[test]
+    fn map_does_not_copy_value() {
+        let cell = Cell::new(Box::new([1u32, 2, 3]));
+        let heap_addr = {
+            let cell_ref = cell.borrow();
+            &**cell_ref as *const [u32; 3] as usize
+        };
+
+        let cell_ref = CellRef::map(cell.borrow(), Box::as_ref);
+        assert_eq!(heap_addr, cell_ref.addr());
+
+        let cell_ref = cell_ref.map(|array| &array[1]);
+        assert_eq!(heap_addr + std::mem::size_of::<u32>(), cell_ref.addr());
+        assert_eq!(1, cell.flag_raw());
+    }
+
+    #[test]
+    fn is_last_returns_true_for_sole_borrow() {
+        let cell = Cell::new(1u32);
+
+        let cell_ref = cell.borrow();
+
+        assert!(cell_ref.is_last());
+    }
+
+    #[test]
+    fn is_last_returns_false_when_clone_exists() {
+        let cell = Cell::new(1u32);
+
+        let cell_ref = cell.borrow();
+        let cell_ref_clone = cell_ref.clone();
+
+        assert!(!cell_ref.is_last());
+        assert!(!cell_ref_clone.is_last());
+    }
+
+    #[test]
+    fn downcast_ref_returns_some_for_matching_type() {
+        let cell = Cell::new(1u32);
+
+        let cell_ref = cell.borrow_any();
+
+        assert_eq!(Some(&1u32), cell_ref.downcast_ref::<u32>());
+    }
+
+    #[test]
+    fn downcast_ref_returns_none_for_other_type() {
+        let cell = Cell::new(1u32);
+
+        let cell_ref = cell.borrow_any();
+
+        assert_eq!(None, cell_ref.downcast_ref::<i32>());
+    }
+
+    #[test]
+    fn downcast_transfers_borrow_for_matching_type() {
+        let cell = Cell::new(1u32);
+
+        let cell_ref = cell
+            .borrow_any()
+            .downcast::<u32>()
+            .map_err(|_| ())
+            .expect("Expected downcast to `u32` to succeed.");
+        assert_eq!(1, *cell_ref);
+        assert_eq!(1, cell.flag.load(Ordering::SeqCst));
+
+        drop(cell_ref);
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn downcast_returns_original_for_other_type() {
+        let cell = Cell::new(1u32);
+
+        let cell_ref = cell
+            .borrow_any()
+            .downcast::<i32>()
+            .map(|_| ())
+            .expect_err("Expected downcast to `i32` to fail.");
+        assert_eq!(Some(&1u32), cell_ref.downcast_ref::<u32>());
+        assert_eq!(1, cell.flag.load(Ordering::SeqCst));
+
+        drop(cell_ref);
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn from_array_cell_ref_preserves_borrow() {
+        let cell = Cell::new([1u32, 2, 3, 4]);
+        let cell_ref = cell.borrow();
+        let cell_ref_other = cell.borrow();
+
+        let slice: CellRef<'_, [u32]> = cell_ref.into();
+        assert_eq!(&[1, 2, 3, 4], &*slice);
+        assert_eq!(2, cell.flag_raw());
+
+        drop(cell_ref_other);
+        assert_eq!(1, cell.flag_raw());
+
+        drop(slice);
+        assert_eq!(0, cell.flag_raw());
+    }
+
+    #[test]
+    fn try_from_cell_borrows_immutably() -> Result<(), BorrowFail> {
+        let cell = Cell::new(1u32);
+
+        let cell_ref: CellRef<'_, u32> = (&cell).try_into()?;
+        let cell_ref_other = CellRef::try_from(&cell)?;
+        assert_eq!(1, *cell_ref);
+        assert_eq!(1, *cell_ref_other);
+        assert_eq!(2, cell.flag_raw());
+
+        drop((cell_ref, cell_ref_other));
+        assert_eq!(0, cell.flag_raw());
+
+        Ok(())
+    }
+
+    #[test]
+    fn try_from_cell_returns_err_when_borrowed_mutably() {
+        let cell = Cell::new(1u32);
+
+        let _cell_ref_mut = cell.borrow_mut();
+
+        assert!(matches!(
+            CellRef::try_from(&cell),
+            Err(BorrowFail::BorrowConflictImm)
+        ));
+    }
+
+    #[test]
+    fn from_cell_ref_mut_downgrades_borrow() {
+        let cell = Cell::new(1u32);
+
+        let mut cell_ref_mut = cell.borrow_mut();
+        *cell_ref_mut += 1;
+
+        let cell_ref = CellRef::from(cell_ref_mut);
+        assert_eq!(1, cell.flag.load(Ordering::SeqCst));
+        assert_eq!(2, *cell_ref);
+
+        let cell_ref_other = cell
+            .try_borrow()
+            .expect("Expected shared borrow to succeed.");
+        assert_eq!(2, *cell_ref_other);
+        assert!(cell.try_borrow_mut().is_err());
+
+        drop((cell_ref, cell_ref_other));
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Cannot downgrade a `CellRefMut` that shares its borrow with another `CellRefMut`."
+    )]
+    fn from_cell_ref_mut_panics_when_borrow_is_shared() {
+        let cell = Cell::new(vec![1u32, 2]);
+
+        let (first, _rest) = cell
+            .borrow_mut()
+            .map(Vec::as_mut_slice)
+            .split_first_mut()
+            .expect("Expected slice to be non-empty.");
+
+        let _cell_ref: CellRef<'_, u32> = first.into();
+    }
+
+    #[test]
+    fn get_projects_borrow_into_present_value() {
+        let cell = Cell::new(HashMap::from([(String::from("a"), 1u32)]));
+
+        let value = CellRef::get(cell.borrow(), "a")
+            .map_err(|_| ())
+            .expect("Expected key `a` to be present.");
+        assert_eq!(1, *value);
+        assert_eq!(1, cell.flag.load(Ordering::SeqCst));
+
+        drop(value);
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn get_returns_original_for_absent_key() {
+        let cell = Cell::new(HashMap::from([(String::from("a"), 1u32)]));
+
+        let map = CellRef::get(cell.borrow(), "b")
+            .map(|_| ())
+            .expect_err("Expected key `b` to be absent.");
+        assert_eq!(1, map.len());
+        assert_eq!(1, cell.flag.load(Ordering::SeqCst));
+
+        drop(map);
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn as_pin_returns_pinned_reference() {
+        let cell = Cell::new(Unmovable {
+            value: 1,
+            _pinned: PhantomPinned,
+        });
+
+        let cell_ref = cell.borrow();
+        let pinned: Pin<&Unmovable> = unsafe { cell_ref.as_pin() };
+
+        assert_eq!(1, pinned.value);
+        assert_eq!(
+            &*cell_ref as *const Unmovable,
+            pinned.get_ref() as *const Unmovable
+        );
+    }
+
+    #[test]
+    fn into_cow_returns_owned_and_releases_borrow() {
+        let cell = Cell::new(String::from("a"));
+
+        let cow: Cow<'_, str> = cell.borrow().map(String::as_str).into_cow();
+
+        assert!(matches!(cow, Cow::Owned(_)));
+        assert_eq!("a", &*cow);
+        assert_eq!(0, cell.flag_raw());
+    }
+
+    #[test]
+    fn into_cow_returns_owned_for_unsized() {
+        let cell = Cell::new(vec![1u32, 2]);
+
+        let cow: Cow<'_, [u32]> = cell.borrow().map(Vec::as_slice).into_cow();
+
+        assert_eq!(&[1, 2], &*cow);
+        assert_eq!(0, cell.flag_raw());
+    }
+
+    #[test]
+    fn map_copy_returns_value_and_releases_borrow() {
+        let cell = Cell::new((1u32, 'a'));
+        let cell_ref = cell.borrow();
+        let cell_ref_other = cell.borrow();
+        assert_eq!(2, cell.flag_raw());
+
+        let n = cell_ref.map_copy(|t| t.0);
+        assert_eq!(1, n);
+        assert_eq!(1, cell.flag_raw());
+
+        let ch = cell_ref_other.map_copy(|t| t.1);
+        assert_eq!('a', ch);
+        assert_eq!(0, cell.flag_raw());
+    }
+
+    #[test]
+    fn read_returns_computed_value_and_releases_borrow() {
+        struct Person {
+            name: &'static str,
+            age: u32,
+        }
+
+        let cell = Cell::new(Person {
+            name: "Ada",
+            age: 36,
+        });
+        let cell_ref = cell.borrow();
+        assert_eq!(1, cell.flag_raw());
+
+        let description = cell_ref.read(|p| format!("{} ({})", p.name, p.age));
+        assert_eq!("Ada (36)", description);
+        assert_eq!(0, cell.flag_raw());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "single_thread", allow(clippy::arc_with_non_send_sync))]
+    fn into_owned_transfers_borrow_for_matching_cell() {
+        let cell = Arc::new(Cell::new(vec![1u32, 2]));
+        let cell_ref = cell.borrow();
+        let cell_ref_other = cell.borrow();
+
+        let owned = cell_ref.into_owned(Arc::clone(&cell));
+        assert_eq!(2, cell.flag_raw());
+        assert_eq!(2, Arc::strong_count(&cell));
+
+        drop(cell_ref_other);
+        assert_eq!(vec![1, 2], *owned);
+        assert_eq!(1, cell.flag_raw());
+
+        drop(owned);
+        assert_eq!(0, cell.flag_raw());
+        assert_eq!(1, Arc::strong_count(&cell));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "single_thread", allow(clippy::arc_with_non_send_sync))]
+    fn into_owned_panics_for_other_cell() {
+        let cell = Arc::new(Cell::new(1u32));
+        let cell_other = Arc::new(Cell::new(1u32));
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            cell.borrow().into_owned(Arc::clone(&cell_other))
+        }));
+
+        let payload = result.expect_err("Expected `into_owned` to panic.");
+        assert_eq!(
+            Some(&"Expected `CellRef` to borrow the whole value of the given `Cell`."),
+            payload.downcast_ref::<&str>()
+        );
+        assert_eq!(0, cell.flag_raw());
+        assert_eq!(0, cell_other.flag_raw());
+        assert_eq!(1, Arc::strong_count(&cell_other));
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected `CellRef` to borrow the whole value of the given `Cell`.")]
+    #[cfg_attr(feature = "single_thread", allow(clippy::arc_with_non_send_sync))]
+    fn into_owned_panics_for_mapped_cell_ref() {
+        let cell: Arc<Cell<[u32]>> = Arc::from(Cell::from_boxed_slice(Box::new([1, 2])));
+
+        let cell_ref = cell.borrow().map(|values| &values[..1]);
+        let _owned = cell_ref.into_owned(Arc::clone(&cell));
+    }
+
+    #[test]
+    fn leak_retains_borrow() {
+        let cell = Cell::new(1u32);
+
+        let value: &u32 = cell.borrow().leak();
+
+        assert_eq!(1, *value);
+        assert_eq!(1, cell.flag_raw());
+        assert!(cell.try_borrow_mut().is_err());
+    }
+
+    #[test]
+    fn into_ref_preserves_borrow() {
+        let cell = Cell::new(1u32);
+        let cell_ref = cell.borrow();
+        assert_eq!(1, cell.flag_raw());
+
+        let r#ref = cell_ref.into_ref();
+        assert_eq!(1, cell.flag_raw());
+        assert_eq!(1, *r#ref);
+
+        drop(r#ref);
+        assert_eq!(0, cell.flag_raw());
+    }
+
+    #[test]
+    fn try_clone_returns_ok_when_ref_count_less_than_isize_max() {
+        let flag = &Flag::new(1);
+        let value = &1u32;
+        let cell_ref = CellRef { flag, value };
+
+        assert_eq!(1, cell_ref.flag.load(Ordering::SeqCst));
+
+        let try_clone_result = cell_ref.try_clone();
+
+        let cloned = try_clone_result.expect("try_clone_result to be ok");
+        assert_eq!(2, cloned.flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn try_clone_returns_err_when_ref_count_equals_isize_max() {
+        let flag = &Flag::new(REF_LIMIT_MAX);
+        let value = &1u32;
+        let cell_ref = CellRef { flag, value };
+
+        assert_eq!(REF_LIMIT_MAX, cell_ref.flag.load(Ordering::SeqCst));
+
+        let try_clone_result = cell_ref.try_clone();
+
+        let e = try_clone_result.expect_err("try_clone_result to be err");
+        assert_eq!(RefOverflow::new::<u32>(REF_LIMIT_MAX), e);
+        assert!(e.source().is_none());
+
+        // Ensure that the overflow is not persisted
+        assert_eq!(REF_LIMIT_MAX, cell_ref.flag.load(Ordering::SeqCst));
+    }
+
+    #[cfg(not(feature = "single_thread"))]
+    #[test]
+    fn try_clone_never_exceeds_isize_max_under_contention() {
+        let flag = &Flag::new(REF_LIMIT_MAX - 4);
+        let value = &1u32;
+        let cell_ref = CellRef { flag, value };
+        let done = &AtomicBool::new(false);
+
+        thread::scope(|s| {
+            let monitor = s.spawn(|| {
+                while !done.load(Ordering::SeqCst) {
+                    assert!(flag.load(Ordering::SeqCst) <= REF_LIMIT_MAX);
+                }
+            });
+
+            let cloners = (0..4)
+                .map(|_| {
+                    s.spawn(|| {
+                        (0..1_000).for_each(|_| {
+                            if let Ok(cloned) = cell_ref.try_clone() {
+                                assert!(flag.load(Ordering::SeqCst) <= REF_LIMIT_MAX);
+                                drop(cloned);
+                            }
+                        });
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            cloners
+                .into_iter()
+                .for_each(|cloner| cloner.join().unwrap());
+            done.store(true, Ordering::SeqCst);
+            monitor.join().unwrap();
+        });
+
+        assert_eq!(REF_LIMIT_MAX - 4, flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn clone_returns_cell_ref_when_ref_count_less_than_isize_max() {
+        let flag = &Flag::new(1);
+        let value = &1u32;
+        let cell_ref = CellRef { flag, value };
+
+        assert_eq!(1, cell_ref.flag.load(Ordering::SeqCst));
+
+        let cloned = cell_ref.clone();
+
+        assert_eq!(2, cell_ref.flag.load(Ordering::SeqCst));
+        assert_eq!(2, cloned.flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    #[cfg(not(feature = "abort_on_overflow"))]
+    #[cfg_attr(
+        not(feature = "track_borrows"),
+        should_panic(expected = "Failed to clone `CellRef`: Ref count reached the flag limit")
+    )]
+    #[cfg_attr(
+        feature = "track_borrows",
+        should_panic(
+            expected = "Failed to clone `CellRef`: Ref count for `u32` reached the flag limit"
+        )
+    )]
+    fn clone_panics_when_ref_count_equals_isize_max() {
+        let flag = &Flag::new(REF_LIMIT_MAX);
+        let value = &1u32;
+        let cell_ref = CellRef { flag, value };
+
+        assert_eq!(REF_LIMIT_MAX, cell_ref.flag.load(Ordering::SeqCst));
+
+        let _clone = cell_ref.clone();
+    }
+
+    #[test]
+    #[cfg(feature = "abort_on_overflow")]
+    #[cfg_attr(miri, ignore)]
+    fn clone_aborts_when_ref_count_equals_isize_max() {
+        use std::{env, process::Command};
+
+        // The abort is asserted from a child process running this test.
+        const CHILD_ENV: &str = "RT_REF_ABORT_ON_OVERFLOW_CHILD";
+        if env::var_os(CHILD_ENV).is_some() {
+            let flag = &Flag::new(REF_LIMIT_MAX);
+            let cell_ref = CellRef { flag, value: &1u32 };
+
+            let _clone = cell_ref.clone();
+            return;
+        }
+
+        let output = Command::new(env::current_exe().expect("Expected test executable path."))
+            .args([
+                "cell_ref::tests::clone_aborts_when_ref_count_equals_isize_max",
+                "--exact",
+                "--nocapture",
+            ])
+            .env(CHILD_ENV, "1")
+            .output()
+            .expect("Expected to run test executable.");
+
+        assert!(!output.status.success());
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::ExitStatusExt;
+
+            assert_eq!(Some(6), output.status.signal()); // SIGABRT
+        }
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(
+            stderr.contains("Failed to clone `CellRef`: Ref count"),
+            "Unexpected stderr: {stderr}"
+        );
+        assert!(!stderr.contains("panicked"), "Unexpected stderr: {stderr}");
+    }
+
+    struct Unmovable {
+        value: u32,
+        _pinned: PhantomPinned,
     }
 }
@@ -1,19 +1,32 @@
 use std::{
-    mem,
+    any::Any,
+    fmt,
+    marker::PhantomData,
+    mem::{self, ManuallyDrop},
     ops::{Deref, DerefMut},
-    sync::atomic::{AtomicUsize, Ordering},
+    pin::Pin,
+    ptr::{self, NonNull},
+    sync::{atomic::Ordering, Arc},
+};
+
+use crate::{
+    cell_ref::REF_LIMIT_MAX,
+    flag::{release_writer, Flag},
+    BorrowFail, Cell, CellRef, FrozenGuard, OwnedCellRefMut, RawBorrow, RefMut,
 };
 
 /// A mutable reference to data in a `Cell`.
 ///
 /// Access the value via `std::ops::DerefMut` (e.g. `*val`)
-#[derive(Debug)]
 pub struct CellRefMut<'a, T>
 where
     T: ?Sized + 'a,
 {
-    pub(crate) flag: &'a AtomicUsize,
+    pub(crate) flag: &'a Flag,
     pub(crate) value: &'a mut T,
+    /// Whether the mutable borrow is shared with other `CellRefMut`s, e.g.
+    /// after `map_split`.
+    pub(crate) shared: bool,
 }
 
 impl<'a, T> CellRefMut<'a, T>
@@ -25,9 +38,11 @@ where
     ///
     /// The `Cell` is already mutably borrowed, so this cannot fail.
     ///
-    /// This is an associated function that needs to be used as
-    /// `CellRefMut::map(..)`. A method would interfere with methods of the
-    /// same name on the contents of a `CellRefMut` used through `DerefMut`.
+    /// Prefer calling this as `CellRefMut::map(..)`: as a method, it takes
+    /// precedence over methods of the same name on the contents of a
+    /// `CellRefMut` used through `DerefMut`, such as `Option::map`.
+    /// [`cell_ref_mut_map`] may be called as a method without this ambiguity.
+    ///
     /// Further this preserves the borrow of the value and hence does the
     /// proper cleanup when it's dropped.
     ///
@@ -64,21 +79,512 @@ where
     /// let b2: CellRefMut<'_, u32> = CellRefMut::map(b1, |t| &mut t.0);
     /// assert_eq!(*b2, 5);
     /// ```
+    ///
+    /// [`cell_ref_mut_map`]: Self::cell_ref_mut_map
     pub fn map<U, F>(self, f: F) -> CellRefMut<'a, U>
     where
         F: FnOnce(&mut T) -> &mut U,
         U: ?Sized,
     {
-        let flag = unsafe { &*(self.flag as *const _) };
-        let value = unsafe { &mut *(self.value as *mut _) };
+        let (flag, value, shared) = self.into_parts();
 
-        mem::forget(self);
+        let writer_guard = WriterGuard(flag, shared);
+        let value = f(value);
+        writer_guard.disarm();
 
         CellRefMut {
             flag,
-            value: f(value),
+            value,
+            shared,
+        }
+    }
+
+    /// Makes a new `CellRefMut` for a component of the borrowed data which
+    /// preserves the existing borrow.
+    ///
+    /// This is the same as [`map`], with a name that does not collide with
+    /// methods on the contents of the `CellRefMut`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_ref::{Cell, CellRefMut};
+    ///
+    /// let c = Cell::new(Some(5));
+    /// let b1: CellRefMut<'_, Option<u32>> = c.borrow_mut();
+    ///
+    /// let mut b2: CellRefMut<'_, u32> = b1.cell_ref_mut_map(|n| n.as_mut().unwrap());
+    /// *b2 += 1;
+    ///
+    /// drop(b2);
+    /// assert_eq!(Some(6), *c.borrow());
+    /// ```
+    ///
+    /// [`map`]: Self::map
+    pub fn cell_ref_mut_map<U, F>(self, f: F) -> CellRefMut<'a, U>
+    where
+        F: FnOnce(&mut T) -> &mut U,
+        U: ?Sized,
+    {
+        self.map(f)
+    }
+
+    /// Makes a new `CellRefMut` for an optional component of the borrowed
+    /// data.
+    ///
+    /// If `f` returns `Some`, the existing borrow is transferred to the
+    /// returned `CellRefMut`, like [`map`]. If `f` returns `None`, this
+    /// `CellRefMut` is dropped and the borrow is released.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_ref::{Cell, CellRefMut};
+    ///
+    /// let c = Cell::new(vec![1, 2, 3]);
+    ///
+    /// if let Some(mut second) = c.borrow_mut().and_then(|v| v.get_mut(1)) {
+    ///     *second += 10;
+    /// }
+    /// assert_eq!(vec![1, 12, 3], *c.borrow());
+    ///
+    /// let fourth: Option<CellRefMut<'_, u32>> = c.borrow_mut().and_then(|v| v.get_mut(3));
+    /// assert!(fourth.is_none());
+    /// assert!(c.try_borrow_mut().is_ok());
+    /// ```
+    ///
+    /// [`map`]: Self::map
+    pub fn and_then<U, F>(self, f: F) -> Option<CellRefMut<'a, U>>
+    where
+        F: FnOnce(&mut T) -> Option<&mut U>,
+        U: ?Sized,
+    {
+        let (flag, value, shared) = self.into_parts();
+
+        // The borrow is released by the guard if `f` returns `None`.
+        let writer_guard = WriterGuard(flag, shared);
+        let value = f(value)?;
+        writer_guard.disarm();
+
+        Some(CellRefMut {
+            flag,
+            value,
+            shared,
+        })
+    }
+
+    /// Splits the borrowed data into two disjoint components.
+    ///
+    /// Both returned `CellRefMut`s share the existing mutable borrow, which is
+    /// released when both are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_ref::{Cell, CellRefMut};
+    ///
+    /// let c = Cell::new((1, 'a'));
+    ///
+    /// let (mut n, mut ch): (CellRefMut<'_, u32>, CellRefMut<'_, char>) =
+    ///     c.borrow_mut().map_split(|(n, ch)| (n, ch));
+    /// *n += 1;
+    /// *ch = 'b';
+    ///
+    /// drop((n, ch));
+    /// assert_eq!((2, 'b'), *c.borrow());
+    /// ```
+    pub fn map_split<U, V, F>(self, f: F) -> (CellRefMut<'a, U>, CellRefMut<'a, V>)
+    where
+        F: FnOnce(&mut T) -> (&mut U, &mut V),
+        U: ?Sized,
+        V: ?Sized,
+    {
+        let (flag, value, shared) = self.into_parts();
+
+        let writer_guard = WriterGuard(flag, shared);
+        let (u, v) = f(value);
+        writer_guard.disarm();
+
+        add_writer(flag);
+
+        (
+            CellRefMut {
+                flag,
+                value: u,
+                shared: true,
+            },
+            CellRefMut {
+                flag,
+                value: v,
+                shared: true,
+            },
+        )
+    }
+
+    /// Splits the borrowed data into `N` disjoint components.
+    ///
+    /// All returned `CellRefMut`s share the existing mutable borrow, which is
+    /// released when all of them are dropped. If `N` is `0`, the borrow is
+    /// released immediately.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_ref::{Cell, CellRefMut};
+    ///
+    /// let c = Cell::new((1, 2, 3));
+    ///
+    /// let [mut a, mut b, mut c_2]: [CellRefMut<'_, u32>; 3] = c
+    ///     .borrow_mut()
+    ///     .split_array_mut(|(a, b, c)| [a, b, c]);
+    /// *a += 10;
+    /// *b += 20;
+    /// *c_2 += 30;
+    ///
+    /// drop((a, b, c_2));
+    /// assert_eq!((11, 22, 33), *c.borrow());
+    /// ```
+    pub fn split_array_mut<U, F, const N: usize>(self, f: F) -> [CellRefMut<'a, U>; N]
+    where
+        F: FnOnce(&mut T) -> [&mut U; N],
+        U: ?Sized,
+    {
+        let (flag, value, shared) = self.into_parts();
+
+        let writer_guard = WriterGuard(flag, shared);
+        let values = f(value);
+        writer_guard.disarm();
+
+        if N == 0 {
+            release_writer(flag, shared);
+        } else {
+            (1..N).for_each(|_| add_writer(flag));
+        }
+
+        let shared = shared || N > 1;
+        values.map(|value| CellRefMut {
+            flag,
+            value,
+            shared,
+        })
+    }
+
+    /// Downgrades this mutable borrow so that the `Cell` may be borrowed
+    /// immutably elsewhere, until [`FrozenGuard::unfreeze`] is called.
+    ///
+    /// This takes `self` by value so that leaking the returned
+    /// [`FrozenGuard`] cannot leave a `CellRefMut` usable while other
+    /// immutable borrows exist.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this `CellRefMut` shares its borrow with another `CellRefMut`,
+    /// e.g. after [`split_first_mut`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_ref::Cell;
+    ///
+    /// let c = Cell::new(5);
+    /// let mut r_mut = c.borrow_mut();
+    /// *r_mut += 1;
+    ///
+    /// let frozen = r_mut.freeze();
+    /// assert_eq!(6, *c.borrow());
+    ///
+    /// let mut r_mut = frozen.unfreeze();
+    /// assert!(c.try_borrow().is_err());
+    /// *r_mut += 1;
+    /// ```
+    ///
+    /// [`split_first_mut`]: CellRefMut::split_first_mut
+    pub fn freeze(self) -> FrozenGuard<'a, T> {
+        if self
+            .flag
+            .compare_exchange(usize::MAX, 1, Ordering::AcqRel, Ordering::Acquire)
+            .is_err()
+        {
+            panic!(
+                "Cannot freeze a `CellRefMut` that shares its borrow with another `CellRefMut`."
+            );
+        }
+
+        #[cfg(feature = "track_borrows")]
+        self.flag.clear_writer();
+
+        let (flag, value, _shared) = self.into_parts();
+
+        FrozenGuard {
+            flag,
+            value: NonNull::from(value),
+            marker: PhantomData,
         }
     }
+
+    /// Separates this `CellRefMut` into its borrow and the reference to the
+    /// borrowed value, without releasing the borrow.
+    ///
+    /// This is intended for building other guards on top of `CellRefMut`. The
+    /// `Cell` remains mutably borrowed until the parts are passed to
+    /// [`from_raw_parts`] and the reconstructed `CellRefMut` is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_ref::{Cell, CellRefMut};
+    ///
+    /// let c = Cell::new((1, 'a'));
+    ///
+    /// let (raw_borrow, value) = c.borrow_mut().into_raw_parts();
+    /// assert!(c.try_borrow().is_err());
+    ///
+    /// let mut n = unsafe { CellRefMut::from_raw_parts(raw_borrow, &mut value.0) };
+    /// *n += 1;
+    ///
+    /// drop(n);
+    /// assert_eq!((2, 'a'), *c.borrow());
+    /// ```
+    ///
+    /// [`from_raw_parts`]: Self::from_raw_parts
+    pub fn into_raw_parts(self) -> (RawBorrow<'a>, &'a mut T) {
+        let (flag, value, shared) = self.into_parts();
+
+        (RawBorrow { flag, shared }, value)
+    }
+
+    /// Reconstructs a `CellRefMut` from parts returned by
+    /// [`into_raw_parts`].
+    ///
+    /// # Safety
+    ///
+    /// * `raw_borrow` must have been returned by
+    ///   [`CellRefMut::into_raw_parts`], not by `CellRef::into_raw_parts`.
+    /// * `value` must be the reference returned alongside `raw_borrow`, or
+    ///   derived from it, e.g. a reference to one of its fields.
+    ///
+    /// The borrow is released when the returned `CellRefMut` is dropped.
+    ///
+    /// [`into_raw_parts`]: Self::into_raw_parts
+    pub unsafe fn from_raw_parts(raw_borrow: RawBorrow<'a>, value: &'a mut T) -> Self {
+        CellRefMut {
+            flag: raw_borrow.flag,
+            value,
+            shared: raw_borrow.shared,
+        }
+    }
+
+    /// Returns the fields of this `CellRefMut` without releasing the borrow.
+    ///
+    /// Reborrowing `self.value` and then moving `self` into `mem::forget`
+    /// would invalidate the reborrow, so the `&mut T` is read out of a
+    /// `ManuallyDrop` instead.
+    fn into_parts(self) -> (&'a Flag, &'a mut T, bool) {
+        let this = ManuallyDrop::new(self);
+        let value = unsafe { ptr::read(&this.value) };
+
+        (this.flag, value, this.shared)
+    }
+
+    /// Runs `f` on the borrowed value, then releases the borrow and returns
+    /// the result of `f`.
+    ///
+    /// This is for mutating the value and computing a result from it, without
+    /// holding the borrow afterwards.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_ref::Cell;
+    ///
+    /// let c = Cell::new(vec![1, 2]);
+    ///
+    /// let len = c.borrow_mut().take_mapped(|v| {
+    ///     v.push(3);
+    ///     v.len()
+    /// });
+    /// assert_eq!(3, len);
+    /// assert_eq!(vec![1, 2, 3], *c.borrow());
+    /// ```
+    pub fn take_mapped<U, F>(self, f: F) -> U
+    where
+        F: FnOnce(&mut T) -> U,
+    {
+        f(self.value)
+    }
+
+    /// Converts this `CellRefMut` into an [`OwnedCellRefMut`] that keeps
+    /// `cell` alive, transferring the borrow.
+    ///
+    /// This allows a borrow to be held in `'static` contexts, e.g. moved into
+    /// a spawned task.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this `CellRefMut` does not borrow the whole value of `cell`,
+    /// e.g. if it was borrowed from another `Cell`, or mapped to a component
+    /// of the value.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::sync::Arc;
+    ///
+    /// use rt_ref::{Cell, OwnedCellRefMut};
+    ///
+    /// let cell = Arc::new(Cell::new(1));
+    ///
+    /// let mut owned: OwnedCellRefMut<u32> = cell.borrow_mut().into_owned(Arc::clone(&cell));
+    /// drop(cell);
+    ///
+    /// *owned += 1;
+    /// assert_eq!(2, *owned);
+    /// ```
+    pub fn into_owned(self, cell: Arc<Cell<T>>) -> OwnedCellRefMut<T> {
+        assert!(
+            !self.shared
+                && ptr::eq(self.flag, &cell.flag)
+                && ptr::eq(&*self.value, cell.inner.get()),
+            "Expected `CellRefMut` to borrow the whole value of the given `Cell`."
+        );
+
+        mem::forget(self);
+
+        OwnedCellRefMut { cell }
+    }
+
+    /// Returns a mutable reference to the borrowed value.
+    ///
+    /// This is equivalent to `&mut *cell_ref_mut`, and is useful when passing
+    /// the value to functions that take `&mut T` without moving the guard.
+    pub fn value_mut(&mut self) -> &mut T {
+        self.value
+    }
+
+    /// Returns a pinned mutable reference to the borrowed value.
+    ///
+    /// This allows polling a `!Unpin` future stored in a `Cell`.
+    ///
+    /// # Safety
+    ///
+    /// The caller must uphold the [pinning guarantee]: once this is called, the
+    /// value must not be moved out of the `Cell` until it is dropped, e.g. by
+    /// `Cell::into_inner`, `mem::swap`, or through a later mutable borrow.
+    ///
+    /// [pinning guarantee]: std::pin#what-is-pinning
+    pub unsafe fn as_pin_mut(&mut self) -> Pin<&mut T> {
+        Pin::new_unchecked(self.value)
+    }
+}
+
+impl<'a, T> CellRefMut<'a, [T]> {
+    /// Splits the borrowed slice into its first element and the rest, or
+    /// returns `None` if the slice is empty.
+    ///
+    /// Both returned `CellRefMut`s share the existing mutable borrow, which is
+    /// released when both are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_ref::{Cell, CellRefMut};
+    ///
+    /// let c = Cell::new([1, 2, 3]);
+    ///
+    /// let slice: CellRefMut<'_, [u32]> = c.borrow_mut().map(|a| &mut a[..]);
+    /// let (mut first, mut rest) = slice.split_first_mut().unwrap();
+    /// *first += 10;
+    /// rest[1] += 10;
+    ///
+    /// drop((first, rest));
+    /// assert_eq!([11, 2, 13], *c.borrow());
+    /// ```
+    pub fn split_first_mut(self) -> Option<(CellRefMut<'a, T>, CellRefMut<'a, [T]>)> {
+        let (flag, value, shared) = self.into_parts();
+
+        let Some((first, rest)) = value.split_first_mut() else {
+            release_writer(flag, shared);
+            return None;
+        };
+
+        add_writer(flag);
+
+        Some((
+            CellRefMut {
+                flag,
+                value: first,
+                shared: true,
+            },
+            CellRefMut {
+                flag,
+                value: rest,
+                shared: true,
+            },
+        ))
+    }
+}
+
+impl<'a, T> CellRefMut<'a, T> {
+    /// Converts this `CellRefMut` into a [`RefMut`], preserving the existing
+    /// borrow.
+    pub fn into_ref_mut(self) -> RefMut<'a, T> {
+        RefMut::new(self)
+    }
+}
+
+impl<'a> CellRefMut<'a, dyn Any> {
+    /// Returns a mutable reference to the borrowed value if it is of type
+    /// `U`, or `None` if it isn't.
+    pub fn downcast_mut<U>(&mut self) -> Option<&mut U>
+    where
+        U: Any,
+    {
+        self.value.downcast_mut::<U>()
+    }
+}
+
+impl<'a, 'b, T> PartialEq<CellRef<'b, T>> for CellRefMut<'a, T>
+where
+    T: PartialEq + ?Sized,
+{
+    fn eq(&self, other: &CellRef<'b, T>) -> bool {
+        let r_self: &T = self;
+        let r_other: &T = other;
+        r_self == r_other
+    }
+}
+
+impl<'a, T, const N: usize> From<CellRefMut<'a, [T; N]>> for CellRefMut<'a, [T]> {
+    /// Coerces the borrowed array to a slice, preserving the existing borrow.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rt_ref::{Cell, CellRefMut};
+    ///
+    /// let c = Cell::new([3, 1, 2]);
+    ///
+    /// let mut slice: CellRefMut<'_, [u32]> = c.borrow_mut().into();
+    /// slice.sort();
+    /// drop(slice);
+    ///
+    /// assert_eq!([1, 2, 3], *c.borrow());
+    /// ```
+    fn from(cell_ref_mut: CellRefMut<'a, [T; N]>) -> Self {
+        cell_ref_mut.map(|array| array as &mut [T])
+    }
+}
+
+impl<'a, T> TryFrom<&'a Cell<T>> for CellRefMut<'a, T>
+where
+    T: ?Sized,
+{
+    type Error = BorrowFail;
+
+    /// Borrows the `Cell` mutably, like [`Cell::try_borrow_mut`].
+    fn try_from(cell: &'a Cell<T>) -> Result<Self, BorrowFail> {
+        cell.try_borrow_mut()
+    }
 }
 
 impl<'a, T> Deref for CellRefMut<'a, T>
@@ -101,11 +607,598 @@ where
     }
 }
 
+impl<'a, W> fmt::Write for CellRefMut<'a, W>
+where
+    W: fmt::Write + ?Sized,
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.value.write_str(s)
+    }
+}
+
+#[cfg(not(feature = "unsafe_debug"))]
+impl<'a, T> fmt::Debug for CellRefMut<'a, T>
+where
+    T: ?Sized,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CellRefMut")
+            .field("flag", self.flag)
+            .field("value", &format_args!(".."))
+            .finish()
+    }
+}
+
+#[cfg(feature = "unsafe_debug")]
+impl<'a, T> fmt::Debug for CellRefMut<'a, T>
+where
+    T: ?Sized + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CellRefMut")
+            .field("flag", self.flag)
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
 impl<'a, T> Drop for CellRefMut<'a, T>
 where
     T: ?Sized,
 {
     fn drop(&mut self) {
-        self.flag.store(0, Ordering::Release)
+        release_writer(self.flag, self.shared);
+    }
+}
+
+/// Records an additional `CellRefMut` sharing the mutable borrow.
+///
+/// A mutable borrow is represented by flag values above `REF_LIMIT_MAX`,
+/// counting down from `usize::MAX` for each `CellRefMut`.
+fn add_writer(flag: &Flag) {
+    let previous_value = flag.fetch_sub(1, Ordering::Relaxed);
+    if previous_value <= REF_LIMIT_MAX + 1 {
+        flag.fetch_add(1, Ordering::Relaxed);
+        panic!("Failed to split `CellRefMut`: Ref count exceeded `isize::MAX`.");
+    }
+}
+
+/// Releases one `CellRefMut`'s share of the mutable borrow when dropped.
+///
+/// This is held while a closure runs on a value taken out of a `CellRefMut`,
+/// so that the borrow is released if the closure panics.
+struct WriterGuard<'a>(&'a Flag, bool);
+
+impl WriterGuard<'_> {
+    /// Keeps the borrow, once it is transferred to a new `CellRefMut`.
+    fn disarm(self) {
+        mem::forget(self);
+    }
+}
+
+impl Drop for WriterGuard<'_> {
+    fn drop(&mut self) {
+        release_writer(self.0, self.1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        any::Any,
+        fmt::{self, Write},
+        future::Future,
+        panic::{self, AssertUnwindSafe},
+        sync::{atomic::Ordering, Arc},
+        task::{Context, Poll, Waker},
+    };
+
+    use crate::{BorrowFail, Cell, CellRefMut, RefMut};
+
+    #[test]
+    fn fmt_write_forwards_to_inner_value() -> fmt::Result {
+        let cell = Cell::new(String::from("n"));
+
+        let mut cell_ref_mut = cell.borrow_mut();
+        write!(cell_ref_mut, " = {}", 1)?;
+        assert_eq!("n = 1", *cell_ref_mut);
+
+        drop(cell_ref_mut);
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+        assert_eq!("n = 1", *cell.borrow());
+
+        Ok(())
+    }
+
+    #[test]
+    fn downcast_mut_returns_some_for_matching_type() {
+        let cell = Cell::new(1u32);
+
+        let mut cell_ref_mut: CellRefMut<'_, dyn Any> =
+            cell.borrow_mut().map(|value| value as &mut dyn Any);
+        if let Some(value) = cell_ref_mut.downcast_mut::<u32>() {
+            *value += 1;
+        }
+        drop(cell_ref_mut);
+
+        assert_eq!(2, *cell.borrow());
+    }
+
+    #[test]
+    fn downcast_mut_returns_none_for_other_type() {
+        let cell = Cell::new(1u32);
+
+        let mut cell_ref_mut: CellRefMut<'_, dyn Any> =
+            cell.borrow_mut().map(|value| value as &mut dyn Any);
+
+        assert!(cell_ref_mut.downcast_mut::<i32>().is_none());
+    }
+
+    #[test]
+    fn cell_ref_mut_map_does_not_collide_with_option_map() {
+        let cell = Cell::new(Some(5u32));
+
+        let cell_ref_mut = cell.borrow_mut();
+        assert_eq!(Some(6), (*cell_ref_mut).map(|n| n + 1));
+
+        let mut n = cell_ref_mut.cell_ref_mut_map(|n| n.as_mut().expect("Expected `Some`."));
+        *n += 1;
+        assert_eq!(usize::MAX, cell.flag.load(Ordering::SeqCst));
+
+        drop(n);
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+        assert_eq!(Some(6), *cell.borrow());
+    }
+
+    #[test]
+    fn partial_eq_cell_ref_compares_value() {
+        let cell = Cell::new(1u32);
+        let cell_equal = Cell::new(1u32);
+        let cell_unequal = Cell::new(2u32);
+
+        let cell_ref_mut = cell.borrow_mut();
+
+        assert!(cell_ref_mut == cell_equal.borrow());
+        assert!(cell_ref_mut != cell_unequal.borrow());
+    }
+
+    #[test]
+    fn from_array_cell_ref_mut_preserves_borrow() {
+        let cell = Cell::new([1u32, 2, 3, 4]);
+
+        let mut slice: CellRefMut<'_, [u32]> = cell.borrow_mut().into();
+        slice[1..].fill(0);
+        assert_eq!(usize::MAX, cell.flag_raw());
+
+        drop(slice);
+        assert_eq!(0, cell.flag_raw());
+        assert_eq!([1, 0, 0, 0], *cell.borrow());
+    }
+
+    #[test]
+    fn try_from_cell_borrows_mutably() -> Result<(), BorrowFail> {
+        let cell = Cell::new(1u32);
+
+        let mut cell_ref_mut: CellRefMut<'_, u32> = (&cell).try_into()?;
+        *cell_ref_mut += 1;
+        assert_eq!(usize::MAX, cell.flag_raw());
+
+        drop(cell_ref_mut);
+        assert_eq!(0, cell.flag_raw());
+        assert_eq!(2, *cell.borrow());
+
+        Ok(())
+    }
+
+    #[test]
+    fn try_from_cell_returns_err_when_borrowed() {
+        let cell = Cell::new(1u32);
+
+        let _cell_ref = cell.borrow();
+
+        assert!(matches!(
+            CellRefMut::try_from(&cell),
+            Err(BorrowFail::ReadLocked(1))
+        ));
+    }
+
+    #[test]
+    fn raw_parts_round_trip_keeps_flag_balanced() {
+        let cell = Cell::new((1u32, 'a'));
+
+        let (raw_borrow, value) = cell.borrow_mut().into_raw_parts();
+        assert_eq!(usize::MAX, cell.flag.load(Ordering::SeqCst));
+
+        let mut n = unsafe { CellRefMut::from_raw_parts(raw_borrow, &mut value.0) };
+        *n += 1;
+        assert_eq!(usize::MAX, cell.flag.load(Ordering::SeqCst));
+
+        drop(n);
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+        assert_eq!((2, 'a'), *cell.borrow());
+    }
+
+    // The split tests below hold multiple `&mut` into disjoint parts of one
+    // `UnsafeCell`, so also run them under Miri:
+    // `cargo +nightly miri test --lib cell_ref_mut::tests::split`
+    #[test]
+    fn split_map_split_interleaved_mutation_drop_first_then_second() {
+        let cell = Cell::new([0u32; 4]);
+
+        let (mut left, mut right) = cell.borrow_mut().map_split(|a| a.split_at_mut(2));
+        for n in 1..=3 {
+            left[0] += n;
+            right[1] += n * 10;
+            left[1] += n;
+            right[0] += n * 10;
+        }
+
+        drop(left);
+        right[0] += 1;
+        drop(right);
+
+        assert_eq!([6, 6, 61, 60], *cell.borrow());
+        assert_eq!(0, cell.flag_raw());
+    }
+
+    #[test]
+    fn split_map_split_interleaved_mutation_drop_second_then_first() {
+        let cell = Cell::new([0u32; 4]);
+
+        let (mut left, mut right) = cell.borrow_mut().map_split(|a| a.split_at_mut(2));
+        for n in 1..=3 {
+            right[1] += n * 10;
+            left[0] += n;
+            right[0] += n * 10;
+            left[1] += n;
+        }
+
+        drop(right);
+        left[0] += 1;
+        drop(left);
+
+        assert_eq!([7, 6, 60, 60], *cell.borrow());
+        assert_eq!(0, cell.flag_raw());
+    }
+
+    #[test]
+    fn split_map_split_nested_splits_mutate_disjoint_parts() {
+        let cell = Cell::new(vec![0u32; 4]);
+
+        let (left, right) = cell.borrow_mut().map_split(|v| v.split_at_mut(2));
+        let (mut a, mut b) = left.map_split(|s| s.split_at_mut(1));
+        let (mut c, mut d) = right.map_split(|s| s.split_at_mut(1));
+        assert_eq!(usize::MAX - 3, cell.flag_raw());
+
+        for n in 1..=2 {
+            d[0] += n;
+            a[0] += n;
+            c[0] += n;
+            b[0] += n;
+        }
+
+        drop(c);
+        drop(a);
+        drop(d);
+        b[0] += 1;
+        drop(b);
+
+        assert_eq!(vec![3, 4, 3, 3], *cell.borrow());
+        assert_eq!(0, cell.flag_raw());
+    }
+
+    #[test]
+    fn split_array_mut_interleaved_mutation_drops_in_any_order() {
+        let cell = Cell::new((0u32, 0u32, 0u32));
+
+        let [mut a, mut b, mut c] = cell.borrow_mut().split_array_mut(|(a, b, c)| [a, b, c]);
+        for n in 1..=3 {
+            *c += n;
+            *a += n;
+            *b += n;
+        }
+
+        drop(b);
+        *c += 1;
+        drop(a);
+        *c += 1;
+        drop(c);
+
+        assert_eq!((6, 6, 8), *cell.borrow());
+        assert_eq!(0, cell.flag_raw());
+    }
+
+    #[test]
+    fn split_first_mut_interleaved_mutation_drop_rest_first() {
+        let cell = Cell::new(vec![0u32; 3]);
+
+        let (mut first, mut rest) = cell
+            .borrow_mut()
+            .map(Vec::as_mut_slice)
+            .split_first_mut()
+            .expect("Expected non-empty slice.");
+        for n in 1..=3 {
+            rest[1] += n;
+            *first += n;
+            rest[0] += n;
+        }
+
+        drop(rest);
+        *first += 1;
+        drop(first);
+
+        assert_eq!(vec![7, 6, 6], *cell.borrow());
+        assert_eq!(0, cell.flag_raw());
+    }
+
+    #[test]
+    fn split_ref_mut_map_split_interleaved_mutation() {
+        let cell = Cell::new((0u32, String::new()));
+
+        let (mut n, mut s) = RefMut::new(cell.borrow_mut()).map_split(|(n, s)| (n, s));
+        for ch in ['a', 'b'] {
+            s.push(ch);
+            *n += 1;
+        }
+
+        drop(s);
+        *n += 1;
+        drop(n);
+
+        assert_eq!((3, String::from("ab")), *cell.borrow());
+        assert_eq!(0, cell.flag_raw());
+    }
+
+    #[test]
+    fn map_split_shares_borrow_until_both_dropped() {
+        let cell = Cell::new((1u32, 'a'));
+
+        let (mut n, mut ch) = cell.borrow_mut().map_split(|(n, ch)| (n, ch));
+        assert_eq!(usize::MAX - 1, cell.flag.load(Ordering::SeqCst));
+
+        *n += 1;
+        *ch = 'b';
+
+        drop(n);
+        assert_eq!(usize::MAX, cell.flag.load(Ordering::SeqCst));
+
+        drop(ch);
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+        assert_eq!((2, 'b'), *cell.borrow());
+    }
+
+    #[test]
+    fn split_array_mut_shares_borrow_until_all_dropped() {
+        let cell = Cell::new((1u32, 2u32, 3u32));
+
+        let [mut a, mut b, mut c] = cell.borrow_mut().split_array_mut(|(a, b, c)| [a, b, c]);
+        assert_eq!(usize::MAX - 2, cell.flag.load(Ordering::SeqCst));
+
+        *a += 10;
+        *b += 20;
+        *c += 30;
+
+        drop(a);
+        drop(c);
+        assert_eq!(usize::MAX, cell.flag.load(Ordering::SeqCst));
+        assert!(cell.try_borrow().is_err());
+
+        drop(b);
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+        assert_eq!((11, 22, 33), *cell.borrow());
+    }
+
+    #[test]
+    fn split_array_mut_releases_borrow_when_empty() {
+        let cell = Cell::new(1u32);
+
+        let [] = cell
+            .borrow_mut()
+            .split_array_mut(|_| -> [&mut u32; 0] { [] });
+
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn split_raw_parts_keep_shared_borrow() {
+        let c = Cell::new((1u32, 'a'));
+
+        let (n, ch) = c.borrow_mut().map_split(|(n, ch)| (n, ch));
+        let (raw_borrow, value) = n.into_raw_parts();
+        let n = unsafe { CellRefMut::from_raw_parts(raw_borrow, value) };
+
+        drop(n);
+        assert!(c.try_borrow().is_err());
+
+        drop(ch);
+        assert_eq!(0, c.flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn cell_ref_mut_is_three_words() {
+        assert_eq!(
+            3 * std::mem::size_of::<usize>(),
+            std::mem::size_of::<CellRefMut<'_, u32>>()
+        );
+    }
+
+    #[test]
+    fn take_mapped_returns_result_and_releases_borrow() {
+        let cell = Cell::new(vec![1, 2]);
+
+        let popped = cell.borrow_mut().take_mapped(Vec::pop);
+
+        assert_eq!(Some(2), popped);
+        assert_eq!(vec![1], *cell.borrow());
+        assert_eq!(0, cell.flag_raw());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "single_thread", allow(clippy::arc_with_non_send_sync))]
+    fn into_owned_transfers_borrow_for_matching_cell() {
+        let cell = Arc::new(Cell::new(vec![1u32, 2]));
+
+        let mut owned = cell.borrow_mut().into_owned(Arc::clone(&cell));
+        owned.push(3);
+        assert_eq!(usize::MAX, cell.flag_raw());
+        assert!(cell.try_borrow().is_err());
+
+        drop(owned);
+        assert_eq!(0, cell.flag_raw());
+        assert_eq!(1, Arc::strong_count(&cell));
+        assert_eq!(vec![1, 2, 3], *cell.borrow());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "single_thread", allow(clippy::arc_with_non_send_sync))]
+    fn into_owned_panics_for_other_cell() {
+        let cell = Arc::new(Cell::new(1u32));
+        let cell_other = Arc::new(Cell::new(1u32));
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            cell.borrow_mut().into_owned(Arc::clone(&cell_other))
+        }));
+
+        let payload = result.expect_err("Expected `into_owned` to panic.");
+        assert_eq!(
+            Some(&"Expected `CellRefMut` to borrow the whole value of the given `Cell`."),
+            payload.downcast_ref::<&str>()
+        );
+        assert_eq!(0, cell.flag_raw());
+        assert_eq!(0, cell_other.flag_raw());
+        assert_eq!(1, Arc::strong_count(&cell_other));
+    }
+
+    #[test]
+    fn value_mut_mutation_persists_after_drop() {
+        let cell = Cell::new(vec![1, 2]);
+
+        let mut cell_ref_mut = cell.borrow_mut();
+        push_three(cell_ref_mut.value_mut());
+        drop(cell_ref_mut);
+
+        assert_eq!(vec![1, 2, 3], *cell.borrow());
+        assert_eq!(0, cell.flag_raw());
+    }
+
+    #[test]
+    fn split_first_mut_shares_borrow_until_both_dropped() {
+        let cell = Cell::new(vec![1u32, 2, 3]);
+
+        let (mut first, mut rest) = cell
+            .borrow_mut()
+            .map(Vec::as_mut_slice)
+            .split_first_mut()
+            .unwrap();
+        assert_eq!(usize::MAX - 1, cell.flag_raw());
+
+        *first += 10;
+        rest[0] += 10;
+        assert_eq!(&[12, 3], &*rest);
+
+        drop(first);
+        assert_eq!(usize::MAX, cell.flag_raw());
+        assert!(cell.try_borrow().is_err());
+        assert!(cell.try_borrow_mut().is_err());
+
+        drop(rest);
+        assert_eq!(0, cell.flag_raw());
+        assert_eq!(vec![11, 12, 3], *cell.borrow());
+    }
+
+    #[test]
+    fn split_first_mut_returns_none_for_empty_slice() {
+        let cell = Cell::new(Vec::<u32>::new());
+
+        let split = cell.borrow_mut().map(Vec::as_mut_slice).split_first_mut();
+
+        assert!(split.is_none());
+        assert_eq!(0, cell.flag_raw());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Cannot freeze a `CellRefMut` that shares its borrow with another `CellRefMut`."
+    )]
+    fn freeze_panics_when_borrow_is_shared() {
+        let cell = Cell::new(vec![1u32, 2, 3]);
+
+        let (first, _rest) = cell
+            .borrow_mut()
+            .map(Vec::as_mut_slice)
+            .split_first_mut()
+            .unwrap();
+
+        let _frozen = first.freeze();
+    }
+
+    #[test]
+    fn into_ref_mut_preserves_borrow() {
+        let cell = Cell::new(1u32);
+        let cell_ref_mut = cell.borrow_mut();
+        assert_eq!(usize::MAX, cell.flag_raw());
+
+        let mut ref_mut = cell_ref_mut.into_ref_mut();
+        assert_eq!(usize::MAX, cell.flag_raw());
+        *ref_mut += 1;
+
+        drop(ref_mut);
+        assert_eq!(0, cell.flag_raw());
+        assert_eq!(2, *cell.borrow());
+    }
+
+    #[test]
+    fn as_pin_mut_allows_polling_unpin_future() {
+        let cell = Cell::new(async { 5 });
+
+        let mut cell_ref_mut = cell.borrow_mut();
+        let future = unsafe { cell_ref_mut.as_pin_mut() };
+
+        let mut context = Context::from_waker(Waker::noop());
+        assert_eq!(Poll::Ready(5), future.poll(&mut context));
+    }
+
+    #[cfg(not(feature = "unsafe_debug"))]
+    #[test]
+    fn debug() {
+        let cell = Cell::new(1);
+
+        assert_eq!(
+            format!("CellRefMut {{ flag: {}, value: .. }}", usize::MAX),
+            format!("{:?}", cell.borrow_mut())
+        );
+
+        struct NotDebug;
+        let cell = Cell::new(NotDebug);
+
+        assert_eq!(
+            format!("CellRefMut {{ flag: {}, value: .. }}", usize::MAX),
+            format!("{:?}", cell.borrow_mut())
+        );
+    }
+
+    #[cfg(feature = "unsafe_debug")]
+    #[test]
+    fn unsafe_debug() {
+        let cell = Cell::new(1);
+
+        assert_eq!(
+            format!("CellRefMut {{ flag: {}, value: 1 }}", usize::MAX),
+            format!("{:?}", cell.borrow_mut())
+        );
+
+        #[allow(dead_code)]
+        #[derive(Debug)]
+        struct A(u32);
+        let cell = Cell::new(A(1));
+
+        assert_eq!(
+            format!("CellRefMut {{ flag: {}, value: A(1) }}", usize::MAX),
+            format!("{:?}", cell.borrow_mut())
+        );
+    }
+
+    fn push_three(values: &mut Vec<u32>) {
+        values.push(3);
     }
 }
@@ -0,0 +1,53 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Number of borrows of a `Cell`, and of conflicting borrow attempts.
+///
+/// Returned by [`Cell::stats`].
+///
+/// [`Cell::stats`]: crate::Cell::stats
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CellStats {
+    /// Number of successful immutable borrows.
+    pub borrows: usize,
+    /// Number of successful mutable borrows.
+    pub borrows_mut: usize,
+    /// Number of borrows that failed due to an existing borrow.
+    pub conflicts: usize,
+}
+
+/// Counters backing [`CellStats`].
+#[derive(Debug, Default)]
+pub(crate) struct CellStatsCounters {
+    borrows: AtomicUsize,
+    borrows_mut: AtomicUsize,
+    conflicts: AtomicUsize,
+}
+
+impl CellStatsCounters {
+    /// Records the result of an immutable borrow attempt.
+    pub(crate) fn record_borrow(&self, acquired: bool) {
+        if acquired {
+            self.borrows.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.conflicts.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Records the result of a mutable borrow attempt.
+    pub(crate) fn record_borrow_mut(&self, acquired: bool) {
+        if acquired {
+            self.borrows_mut.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.conflicts.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Returns a snapshot of the counters.
+    pub(crate) fn snapshot(&self) -> CellStats {
+        CellStats {
+            borrows: self.borrows.load(Ordering::Relaxed),
+            borrows_mut: self.borrows_mut.load(Ordering::Relaxed),
+            conflicts: self.conflicts.load(Ordering::Relaxed),
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::CellRefMut;
+
+/// A mutable reference to data in a `Cell`, which runs a finalizer before the
+/// borrow is released.
+///
+/// Returned by [`Cell::borrow_mut_with_finalizer`]. The finalizer runs exactly
+/// once when this is dropped, including while unwinding from a panic.
+///
+/// Access the value via `std::ops::DerefMut` (e.g. `*val`)
+///
+/// [`Cell::borrow_mut_with_finalizer`]: crate::Cell::borrow_mut_with_finalizer
+pub struct FinalizingRefMut<'a, T, F>
+where
+    F: FnOnce(&mut T),
+{
+    pub(crate) cell_ref_mut: CellRefMut<'a, T>,
+    pub(crate) on_release: Option<F>,
+}
+
+impl<'a, T, F> Deref for FinalizingRefMut<'a, T, F>
+where
+    F: FnOnce(&mut T),
+{
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.cell_ref_mut
+    }
+}
+
+impl<'a, T, F> DerefMut for FinalizingRefMut<'a, T, F>
+where
+    F: FnOnce(&mut T),
+{
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.cell_ref_mut
+    }
+}
+
+impl<'a, T, F> Drop for FinalizingRefMut<'a, T, F>
+where
+    F: FnOnce(&mut T),
+{
+    fn drop(&mut self) {
+        // `cell_ref_mut` is dropped after this, releasing the borrow.
+        if let Some(on_release) = self.on_release.take() {
+            on_release(&mut self.cell_ref_mut);
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use std::ops::Deref;
+
+use crate::{flag_limit::FlagLimit, Cell, OverflowPolicy};
+
+/// A [`Cell`] that allows at most `MAX_READERS` immutable borrows.
+///
+/// This is equivalent to a `Cell` built with [`CellBuilder::flag_limit`] and
+/// [`OverflowPolicy::Error`], with the limit part of the type. Borrows beyond
+/// the limit fail with [`BorrowFail::ReadLimitReached`], and
+/// [`CellRef::try_clone`] returns [`RefOverflow`].
+///
+/// `MAX_READERS` must be at least `1`, and values above `isize::MAX` are
+/// treated as `isize::MAX`.
+///
+/// ```rust,compile_fail
+/// use rt_ref::FixedCell;
+///
+/// let cell = FixedCell::<u32, 0>::new(1); // fails to build
+/// ```
+///
+/// The value is borrowed through the `Cell` via `Deref`.
+///
+/// # Examples
+///
+/// ```rust
+/// use rt_ref::FixedCell;
+///
+/// let cell = FixedCell::<u32, 2>::new(1);
+///
+/// let r_0 = cell.borrow();
+/// let r_1 = r_0.try_clone().unwrap();
+/// assert!(r_1.try_clone().is_err());
+/// assert!(cell.try_borrow().is_err());
+/// ```
+///
+/// [`BorrowFail::ReadLimitReached`]: crate::BorrowFail::ReadLimitReached
+/// [`CellBuilder::flag_limit`]: crate::CellBuilder::flag_limit
+/// [`CellRef::try_clone`]: crate::CellRef::try_clone
+/// [`RefOverflow`]: crate::RefOverflow
+#[derive(Debug)]
+pub struct FixedCell<T, const MAX_READERS: usize> {
+    cell: Cell<T>,
+}
+
+impl<T, const MAX_READERS: usize> FixedCell<T, MAX_READERS> {
+    /// Fails to compile if `MAX_READERS` is `0`, as the value could never be
+    /// borrowed immutably.
+    const MAX_READERS_NON_ZERO: () = assert!(MAX_READERS > 0, "`MAX_READERS` must be non-zero.");
+
+    /// Create a new cell, similar to `Cell::new`
+    pub fn new(inner: T) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::MAX_READERS_NON_ZERO;
+
+        let limit = FlagLimit::new(MAX_READERS, OverflowPolicy::Error);
+
+        Self {
+            cell: Cell::new_with_limit(inner, limit),
+        }
+    }
+
+    /// Consumes this cell and returns ownership of `T`.
+    pub fn into_inner(self) -> T {
+        self.cell.into_inner()
+    }
+}
+
+impl<T, const MAX_READERS: usize> Deref for FixedCell<T, MAX_READERS> {
+    type Target = Cell<T>;
+
+    fn deref(&self) -> &Cell<T> {
+        &self.cell
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::Ordering;
+
+    use crate::{BorrowFail, RefOverflow};
+
+    use super::FixedCell;
+
+    #[test]
+    fn try_clone_returns_ref_overflow_at_max_readers() {
+        let cell = FixedCell::<u32, 3>::new(1);
+
+        let r_0 = cell.borrow();
+        let r_1 = r_0.try_clone().expect("Expected second reader.");
+        let r_2 = r_1.try_clone().expect("Expected third reader.");
+        assert_eq!(3, cell.flag.load(Ordering::SeqCst));
+
+        assert_eq!(
+            RefOverflow::new::<u32>(3),
+            r_2.try_clone().map(|_| ()).unwrap_err()
+        );
+        assert_eq!(3, cell.flag.load(Ordering::SeqCst));
+
+        drop((r_0, r_1, r_2));
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn try_borrow_fails_at_max_readers() {
+        let cell = FixedCell::<u32, 2>::new(1);
+
+        let _r_0 = cell.borrow();
+        let _r_1 = cell.borrow();
+
+        assert_eq!(
+            Err(BorrowFail::ReadLimitReached(2)),
+            cell.try_borrow().map(|_| ())
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Failed to clone `CellRef`")]
+    fn clone_panics_at_max_readers() {
+        let cell = FixedCell::<u32, 1>::new(1);
+
+        let r_0 = cell.borrow();
+        let _r_1 = r_0.clone();
+    }
+
+    #[test]
+    fn borrow_mut_requires_no_readers() {
+        let cell = FixedCell::<u32, 1>::new(1);
+
+        *cell.borrow_mut() += 1;
+
+        let r = cell.borrow();
+        assert_eq!(
+            Err(BorrowFail::ReadLocked(1)),
+            cell.try_borrow_mut().map(|_| ())
+        );
+
+        drop(r);
+        assert_eq!(2, cell.into_inner());
+    }
+}
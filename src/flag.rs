@@ -0,0 +1,251 @@
+//! Borrow flag of a `Cell`.
+//!
+//! The flag holds the borrow count, along with the `Cell`'s limit on immutable
+//! borrows, so that guards reach both through a single reference. It
+//! dereferences to the count. Guards release their borrows through
+//! [`release_reader`] and [`release_writer`].
+//!
+//! With the `"single_thread"` feature, the count is a non-atomic counter with
+//! the same interface as `AtomicUsize`, so that callers are unchanged.
+
+use std::{fmt, ops::Deref, sync::atomic::Ordering};
+
+#[cfg(not(feature = "single_thread"))]
+pub(crate) use std::sync::atomic::AtomicUsize as Counter;
+
+#[cfg(feature = "single_thread")]
+pub(crate) use self::single_thread::Counter;
+
+use crate::{
+    cell_ref::REF_LIMIT_MAX,
+    flag_limit::{FlagLimit, SATURATED},
+    OverflowPolicy,
+};
+
+/// Borrow count of a `Cell`, and its limit on immutable borrows.
+pub(crate) struct Flag {
+    count: Counter,
+    /// Maximum number of immutable borrows.
+    pub(crate) limit: FlagLimit,
+    /// ID of the thread that acquired the mutable borrow, or `0`.
+    #[cfg(feature = "track_borrows")]
+    writer: Counter,
+    /// Highest number of readers recorded.
+    #[cfg(feature = "leak_guard")]
+    max_readers: Counter,
+}
+
+impl Flag {
+    /// Returns a flag with the given count, and the default limit.
+    pub(crate) const fn new(value: usize) -> Self {
+        Self::with_limit(value, FlagLimit::DEFAULT)
+    }
+
+    /// Returns a flag with the given count and limit.
+    pub(crate) const fn with_limit(value: usize, limit: FlagLimit) -> Self {
+        Flag {
+            count: Counter::new(value),
+            limit,
+            #[cfg(feature = "track_borrows")]
+            writer: Counter::new(0),
+            #[cfg(feature = "leak_guard")]
+            max_readers: Counter::new(0),
+        }
+    }
+
+    /// Records the current thread as the holder of the mutable borrow.
+    ///
+    /// This must be called after the mutable borrow is acquired.
+    #[cfg(feature = "track_borrows")]
+    pub(crate) fn set_writer(&self) {
+        self.writer
+            .store(crate::thread_id::current(), Ordering::Release);
+    }
+
+    /// Clears the holder of the mutable borrow.
+    ///
+    /// This must be called before the mutable borrow is released, so that the
+    /// next writer is never cleared.
+    #[cfg(feature = "track_borrows")]
+    pub(crate) fn clear_writer(&self) {
+        self.writer.store(0, Ordering::Release);
+    }
+
+    /// Returns whether the mutable borrow is held by the current thread.
+    ///
+    /// The writer is read before the count, so a writer that was recorded by
+    /// this thread is only reported while the flag still holds a mutable
+    /// borrow.
+    #[cfg(feature = "track_borrows")]
+    pub(crate) fn is_writer_current_thread(&self) -> bool {
+        self.writer.load(Ordering::Acquire) == crate::thread_id::current()
+            && self.count.load(Ordering::Acquire) > crate::cell_ref::REF_LIMIT_MAX
+    }
+
+    /// Records that the flag holds `readers` immutable borrows.
+    #[cfg(feature = "leak_guard")]
+    pub(crate) fn record_readers(&self, readers: usize) {
+        self.max_readers.fetch_max(readers, Ordering::Relaxed);
+    }
+
+    /// Returns the highest number of readers recorded.
+    #[cfg(feature = "leak_guard")]
+    pub(crate) fn max_readers(&self) -> usize {
+        self.max_readers.load(Ordering::Relaxed)
+    }
+}
+
+impl Deref for Flag {
+    type Target = Counter;
+
+    #[inline]
+    fn deref(&self) -> &Counter {
+        &self.count
+    }
+}
+
+impl fmt::Debug for Flag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.count, f)
+    }
+}
+
+/// Releases one immutable borrow of the flag.
+///
+/// Saturated borrows are never released, see [`OverflowPolicy::Saturate`].
+///
+/// [`OverflowPolicy::Saturate`]: crate::OverflowPolicy::Saturate
+pub(crate) fn release_reader(flag: &Flag) {
+    if flag.limit.policy != OverflowPolicy::Saturate {
+        let previous_value = flag.fetch_sub(1, Ordering::Release);
+        debug_assert_reader(previous_value);
+        return;
+    }
+
+    let mut val = flag.load(Ordering::Relaxed);
+    while val != SATURATED {
+        debug_assert_reader(val);
+
+        match flag.compare_exchange_weak(val, val - 1, Ordering::Release, Ordering::Relaxed) {
+            Ok(_) => return,
+            Err(current) => val = current,
+        }
+    }
+}
+
+/// Releases one `CellRefMut`'s share of the mutable borrow.
+///
+/// An unshared `CellRefMut` is the only writer, and every other borrow attempt
+/// fails without writing to the flag, so the borrow is released with a store,
+/// which is cheaper than `fetch_add`.
+///
+/// A `CellRefMut` that shares the borrow increments the flag instead, and
+/// `usize::MAX` wraps to `0` when the last `CellRefMut` is released. Each
+/// increment is a `Release` read-modify-write, so writes through
+/// `CellRefMut`s released on other threads are visible to the next borrow.
+///
+/// With the `"track_borrows"` feature, the writer is cleared before the borrow
+/// is released, so that a later borrow is not reported as re-entrant.
+pub(crate) fn release_writer(flag: &Flag, shared: bool) {
+    #[cfg(feature = "track_borrows")]
+    flag.clear_writer();
+
+    if shared {
+        flag.fetch_add(1, Ordering::Release);
+    } else {
+        flag.store(0, Ordering::Release);
+    }
+}
+
+/// Checks that the flag held an immutable borrow before it was released, to
+/// catch borrow accounting bugs in debug builds.
+///
+/// If the flag were `0`, or a mutable borrow, decrementing it would corrupt
+/// the flag for all later borrows.
+fn debug_assert_reader(flag: usize) {
+    debug_assert!(
+        flag != 0 && flag <= REF_LIMIT_MAX,
+        "Expected `CellRef` to hold an immutable borrow when dropped, but the borrow flag was {flag}."
+    );
+}
+
+#[cfg(feature = "single_thread")]
+mod single_thread {
+    use std::{cell::Cell, fmt, panic::RefUnwindSafe, sync::atomic::Ordering};
+
+    /// Non-atomic borrow count.
+    ///
+    /// The `Ordering` parameters are ignored, as the count is never accessed
+    /// from multiple threads.
+    #[derive(Default)]
+    pub(crate) struct Counter(Cell<usize>);
+
+    impl Counter {
+        pub(crate) const fn new(value: usize) -> Self {
+            Counter(Cell::new(value))
+        }
+
+        pub(crate) fn load(&self, _order: Ordering) -> usize {
+            self.0.get()
+        }
+
+        pub(crate) fn store(&self, value: usize, _order: Ordering) {
+            self.0.set(value);
+        }
+
+        pub(crate) fn fetch_add(&self, value: usize, _order: Ordering) -> usize {
+            let previous = self.0.get();
+            self.0.set(previous.wrapping_add(value));
+            previous
+        }
+
+        pub(crate) fn fetch_sub(&self, value: usize, _order: Ordering) -> usize {
+            let previous = self.0.get();
+            self.0.set(previous.wrapping_sub(value));
+            previous
+        }
+
+        #[cfg(feature = "leak_guard")]
+        pub(crate) fn fetch_max(&self, value: usize, _order: Ordering) -> usize {
+            let previous = self.0.get();
+            self.0.set(previous.max(value));
+            previous
+        }
+
+        pub(crate) fn compare_exchange(
+            &self,
+            current: usize,
+            new: usize,
+            _success: Ordering,
+            _failure: Ordering,
+        ) -> Result<usize, usize> {
+            let previous = self.0.get();
+            if previous == current {
+                self.0.set(new);
+                Ok(previous)
+            } else {
+                Err(previous)
+            }
+        }
+
+        pub(crate) fn compare_exchange_weak(
+            &self,
+            current: usize,
+            new: usize,
+            success: Ordering,
+            failure: Ordering,
+        ) -> Result<usize, usize> {
+            self.compare_exchange(current, new, success, failure)
+        }
+    }
+
+    // Like `AtomicUsize`, the counter is never left partially updated by a
+    // panic.
+    impl RefUnwindSafe for Counter {}
+
+    impl fmt::Debug for Counter {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            fmt::Debug::fmt(&self.0.get(), f)
+        }
+    }
+}
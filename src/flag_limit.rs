@@ -0,0 +1,39 @@
+use crate::{cell_ref::REF_LIMIT_MAX, OverflowPolicy};
+
+/// Flag value of a saturated immutable borrow.
+///
+/// This is never reached by counting, as saturating limits are capped below
+/// it.
+pub(crate) const SATURATED: usize = REF_LIMIT_MAX;
+
+/// Maximum number of immutable borrows of a `Cell`, and what to do when it is
+/// reached.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct FlagLimit {
+    /// Maximum number of immutable borrows.
+    pub(crate) max: usize,
+    /// Behaviour when cloning a `CellRef` would exceed `max`.
+    pub(crate) policy: OverflowPolicy,
+}
+
+impl FlagLimit {
+    /// Limit used by `Cell::new`.
+    pub(crate) const DEFAULT: FlagLimit = FlagLimit {
+        max: REF_LIMIT_MAX,
+        policy: OverflowPolicy::Error,
+    };
+
+    /// Returns a limit of `max` immutable borrows, clamped to the values
+    /// supported by `policy`.
+    pub(crate) fn new(max: usize, policy: OverflowPolicy) -> Self {
+        let max_supported = match policy {
+            OverflowPolicy::Saturate => SATURATED - 1,
+            OverflowPolicy::Panic | OverflowPolicy::Error => REF_LIMIT_MAX,
+        };
+
+        FlagLimit {
+            max: max.clamp(1, max_supported),
+            policy,
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use std::{hint, marker::PhantomData, mem, ops::Deref, ptr::NonNull, sync::atomic::Ordering};
+
+use crate::{
+    flag::{release_reader, Flag},
+    flag_limit::SATURATED,
+    CellRefMut,
+};
+
+/// An immutable reference to data in a `Cell`, downgraded from a
+/// `CellRefMut`.
+///
+/// Returned by [`CellRefMut::freeze`]. While this is held, the `Cell` may be
+/// borrowed immutably elsewhere. Call [`unfreeze`] to re-acquire exclusive
+/// access. If this is dropped instead, the borrow is released like a `CellRef`.
+///
+/// Access the value via `std::ops::Deref` (e.g. `*val`)
+///
+/// [`unfreeze`]: Self::unfreeze
+#[derive(Debug)]
+pub struct FrozenGuard<'a, T>
+where
+    T: ?Sized + 'a,
+{
+    pub(crate) flag: &'a Flag,
+    /// Stored as a pointer, as the value is aliased by other immutable borrows
+    /// while frozen.
+    pub(crate) value: NonNull<T>,
+    pub(crate) marker: PhantomData<&'a mut T>,
+}
+
+impl<'a, T> FrozenGuard<'a, T>
+where
+    T: ?Sized,
+{
+    /// Re-acquires exclusive access, returning the `CellRefMut`.
+    ///
+    /// # Blocking
+    ///
+    /// This spins until all other immutable borrows of the `Cell` are
+    /// released. If another immutable borrow is held on the same thread, this
+    /// never returns.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the borrow was saturated while frozen, as saturated borrows
+    /// are never released. See [`OverflowPolicy::Saturate`].
+    ///
+    /// [`OverflowPolicy::Saturate`]: crate::OverflowPolicy::Saturate
+    pub fn unfreeze(self) -> CellRefMut<'a, T> {
+        loop {
+            match self.flag.compare_exchange_weak(
+                1,
+                usize::MAX,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => break,
+                Err(SATURATED) => {
+                    panic!("Cannot unfreeze a `FrozenGuard` whose borrow is saturated.")
+                }
+                Err(_) => hint::spin_loop(),
+            }
+        }
+
+        #[cfg(feature = "track_borrows")]
+        self.flag.set_writer();
+
+        let flag = self.flag;
+        let value = unsafe { &mut *self.value.as_ptr() };
+
+        mem::forget(self);
+
+        CellRefMut {
+            flag,
+            value,
+            shared: false,
+        }
+    }
+}
+
+impl<'a, T> Deref for FrozenGuard<'a, T>
+where
+    T: ?Sized,
+{
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { self.value.as_ref() }
+    }
+}
+
+#[cfg(not(feature = "single_thread"))]
+unsafe impl<'a, T> Send for FrozenGuard<'a, T> where T: ?Sized + Send + Sync {}
+
+#[cfg(not(feature = "single_thread"))]
+unsafe impl<'a, T> Sync for FrozenGuard<'a, T> where T: ?Sized + Sync {}
+
+impl<'a, T> Drop for FrozenGuard<'a, T>
+where
+    T: ?Sized,
+{
+    fn drop(&mut self) {
+        release_reader(self.flag);
+    }
+}
@@ -0,0 +1,78 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
+    hash::BuildHasher,
+};
+
+/// Collections with a length, used by [`Cell::len`] and [`Cell::is_empty`].
+///
+/// [`Cell::len`]: crate::Cell::len
+/// [`Cell::is_empty`]: crate::Cell::is_empty
+pub trait HasLen {
+    /// Returns the number of elements in the collection.
+    fn len(&self) -> usize;
+
+    /// Returns `true` if the collection contains no elements.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> HasLen for [T] {
+    fn len(&self) -> usize {
+        <[T]>::len(self)
+    }
+}
+
+impl<T> HasLen for Vec<T> {
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+}
+
+impl<T> HasLen for VecDeque<T> {
+    fn len(&self) -> usize {
+        VecDeque::len(self)
+    }
+}
+
+impl HasLen for str {
+    fn len(&self) -> usize {
+        str::len(self)
+    }
+}
+
+impl HasLen for String {
+    fn len(&self) -> usize {
+        String::len(self)
+    }
+}
+
+impl<K, V, S> HasLen for HashMap<K, V, S>
+where
+    S: BuildHasher,
+{
+    fn len(&self) -> usize {
+        HashMap::len(self)
+    }
+}
+
+impl<T, S> HasLen for HashSet<T, S>
+where
+    S: BuildHasher,
+{
+    fn len(&self) -> usize {
+        HashSet::len(self)
+    }
+}
+
+impl<K, V> HasLen for BTreeMap<K, V> {
+    fn len(&self) -> usize {
+        BTreeMap::len(self)
+    }
+}
+
+impl<T> HasLen for BTreeSet<T> {
+    fn len(&self) -> usize {
+        BTreeSet::len(self)
+    }
+}
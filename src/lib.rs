@@ -57,18 +57,173 @@
 //! ```
 //!
 //!
+//! #### `"track_borrows"`:
+//!
+//! Tracks the thread that borrows a value mutably, so that a conflicting
+//! borrow on the same thread is reported as a re-entrant borrow.
+//!
+//! Also enables `Cell::try_borrow_info`, which returns the number of readers
+//! and the caller's location alongside the borrow.
+//!
+//! `RefOverflow` also holds the name of the borrowed type, which is included in
+//! its message.
+//!
+//! ```rust,should_panic
+//! use rt_ref::Cell;
+//!
+//! let cell = Cell::new(1);
+//!
+//! let _a = cell.borrow_mut();
+//! let _b = cell.borrow(); // panics
+//! ```
+//!
+//!
+//! #### `"single_thread"`:
+//!
+//! Uses a non-atomic borrow flag, which avoids atomic operations when
+//! borrowing, cloning, and dropping references.
+//!
+//! **Note:** `Cell` is no longer `Sync`, and references to its value are no
+//! longer `Send` or `Sync`, so this is only suitable when all borrows happen on
+//! one thread. The public API and borrow semantics are otherwise unchanged.
+//!
+//! **Warning:** This feature is not additive. Cargo enables a feature for
+//! every crate in the dependency graph that depends on `rt_ref`, so a library
+//! that sends a `Cell` across threads fails to compile if any other crate
+//! enables `"single_thread"`. Only enable it in a binary crate, or a leaf crate
+//! that is not depended on by other crates.
+//!
+//!
+//! #### `"metrics"`:
+//!
+//! Counts the borrows of each `Cell`, and conflicting borrow attempts, which
+//! are returned by `Cell::stats`. This is useful to find contended cells.
+//!
+//!
+//! #### `"timing"`:
+//!
+//! Enables `Cell::borrow_mut_timed`, which records the longest duration that
+//! the value was borrowed mutably, returned by `Cell::max_borrow_mut_duration`.
+//! This is useful to find long-held borrows that block other borrows.
+//!
+//!
+//! #### `"testing"`:
+//!
+//! Enables `Cell::assert_unused`, which panics if a cell is still borrowed.
+//! This is useful in test teardown, to detect guards that were not dropped.
+//!
+//!
+//! #### `"borrow_conflict_formatter"`:
+//!
+//! Enables `set_borrow_conflict_formatter`, which customizes the panic message
+//! when a `Cell` is borrowed while it is already borrowed, e.g. to include an
+//! application specific resource ID.
+//!
+//!
+//! #### `"abort_on_overflow"`:
+//!
+//! `CellRef::clone` aborts the process instead of panicking when the number of
+//! references reaches `isize::MAX`. Reaching it implies a serious leak bug, and
+//! aborting prevents a caught panic from continuing with corrupted borrow
+//! state, e.g. across an FFI boundary.
+//!
+//!
+//! #### `"leak_guard"`:
+//!
+//! Records the highest number of immutable borrows held at the same time for
+//! each `Cell`, returned by `Cell::max_readers_seen`. Services can check that
+//! this stays bounded, to detect `CellRef`s that are slowly leaked.
+//!
+//!
+//! #### `"rkyv"`:
+//!
+//! Implements [`rkyv`]'s `Archive`, `Serialize`, and `Deserialize` for `Cell`.
+//! Only the inner value is archived, and the borrow flag is reset when
+//! deserialized.
+//!
+//!
+//! #### `"tracing"`:
+//!
+//! Enables `Cell::borrow_span`, which returns a [`tracing`] span alongside the
+//! borrow, so that subscribers can see how long each borrow is held.
+//!
+//!
+//! [`rkyv`]: https://crates.io/crates/rkyv
+//! [`tracing`]: https://crates.io/crates/tracing
 //! [`rt_map`]: https://crates.io/crates/rt_map
 //! [`rt_vec`]: https://crates.io/crates/rt_vec
 
+pub mod prelude;
+
 pub use crate::{
-    borrow_fail::BorrowFail, cell::Cell, cell_ref::CellRef, cell_ref_mut::CellRefMut, r#ref::Ref,
-    ref_mut::RefMut, ref_overflow::RefOverflow,
+    borrow_fail::BorrowFail,
+    borrow_fail_or::BorrowFailOr,
+    borrow_token::BorrowToken,
+    cell::{try_into_inner, unwrap_all, wrap_all, Cell},
+    cell_builder::CellBuilder,
+    cell_ref::CellRef,
+    cell_ref_mut::CellRefMut,
+    finalizing_ref_mut::FinalizingRefMut,
+    fixed_cell::FixedCell,
+    frozen_guard::FrozenGuard,
+    has_len::HasLen,
+    overflow_policy::OverflowPolicy,
+    owned_cell_ref::OwnedCellRef,
+    owned_cell_ref_mut::OwnedCellRefMut,
+    r#ref::Ref,
+    raw_borrow::RawBorrow,
+    reentrant_ref::ReentrantRef,
+    ref_mut::RefMut,
+    ref_overflow::RefOverflow,
+    weak_cell_ref::WeakCellRef,
 };
 
+#[cfg(feature = "rkyv")]
+pub use crate::archived_cell::ArchivedCell;
+
+#[cfg(feature = "borrow_conflict_formatter")]
+pub use crate::borrow_conflict_formatter::set_borrow_conflict_formatter;
+
+#[cfg(feature = "track_borrows")]
+pub use crate::borrow_info::BorrowInfo;
+
+#[cfg(feature = "metrics")]
+pub use crate::cell_stats::CellStats;
+
+#[cfg(feature = "timing")]
+pub use crate::timed_ref_mut::TimedRefMut;
+
+#[cfg(feature = "rkyv")]
+mod archived_cell;
+#[cfg(feature = "borrow_conflict_formatter")]
+mod borrow_conflict_formatter;
 mod borrow_fail;
+mod borrow_fail_or;
+#[cfg(feature = "track_borrows")]
+mod borrow_info;
+mod borrow_token;
 mod cell;
+mod cell_builder;
 mod cell_ref;
 mod cell_ref_mut;
+#[cfg(feature = "metrics")]
+mod cell_stats;
+mod finalizing_ref_mut;
+mod fixed_cell;
+mod flag;
+mod flag_limit;
+mod frozen_guard;
+mod has_len;
+mod overflow_policy;
+mod owned_cell_ref;
+mod owned_cell_ref_mut;
+mod raw_borrow;
+mod reentrant_ref;
 mod r#ref;
 mod ref_mut;
 mod ref_overflow;
+#[cfg(feature = "track_borrows")]
+mod thread_id;
+#[cfg(feature = "timing")]
+mod timed_ref_mut;
+mod weak_cell_ref;
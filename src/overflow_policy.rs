@@ -0,0 +1,21 @@
+/// Behaviour when cloning a [`CellRef`] would exceed a `Cell`'s flag limit.
+///
+/// See [`CellBuilder::on_overflow`].
+///
+/// [`CellRef`]: crate::CellRef
+/// [`CellBuilder::on_overflow`]: crate::CellBuilder::on_overflow
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum OverflowPolicy {
+    /// `clone` and `try_clone` panic.
+    Panic,
+    /// `clone` and `try_clone` succeed, and the borrow is saturated.
+    ///
+    /// A saturated borrow is never released, so the `Cell` cannot be borrowed
+    /// again after the limit is reached.
+    Saturate,
+    /// `try_clone` returns [`RefOverflow`], and `clone` panics.
+    ///
+    /// [`RefOverflow`]: crate::RefOverflow
+    #[default]
+    Error,
+}
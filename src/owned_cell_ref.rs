@@ -0,0 +1,151 @@
+use std::{
+    fmt,
+    ops::Deref,
+    sync::{atomic::Ordering, Arc},
+};
+
+use crate::{flag::release_reader, Cell};
+
+/// An immutable reference to data in an `Arc<Cell<T>>`, which keeps the `Cell`
+/// alive.
+///
+/// Returned by [`Cell::borrow_owned`]. Unlike a [`CellRef`], this does not
+/// borrow the `Cell`'s handle, so it may be held after the `Arc` it was
+/// borrowed from is dropped, or moved to another thread.
+///
+/// Access the value via `std::ops::Deref` (e.g. `*val`)
+///
+/// [`Cell::borrow_owned`]: crate::Cell::borrow_owned
+pub struct OwnedCellRef<T>
+where
+    T: ?Sized,
+{
+    /// Cell whose value is immutably borrowed by this guard.
+    pub(crate) cell: Arc<Cell<T>>,
+}
+
+impl<T> Deref for OwnedCellRef<T>
+where
+    T: ?Sized,
+{
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // This holds a reader slot, so the value is not mutably borrowed.
+        unsafe { &*self.cell.inner.get() }
+    }
+}
+
+#[cfg(not(feature = "unsafe_debug"))]
+impl<T> fmt::Debug for OwnedCellRef<T>
+where
+    T: ?Sized,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OwnedCellRef")
+            .field("readers", &self.cell.flag.load(Ordering::Relaxed))
+            .finish()
+    }
+}
+
+#[cfg(feature = "unsafe_debug")]
+impl<T> fmt::Debug for OwnedCellRef<T>
+where
+    T: ?Sized + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OwnedCellRef")
+            .field("readers", &self.cell.flag.load(Ordering::Relaxed))
+            .field("value", &&**self)
+            .finish()
+    }
+}
+
+impl<T> Drop for OwnedCellRef<T>
+where
+    T: ?Sized,
+{
+    fn drop(&mut self) {
+        release_reader(&self.cell.flag);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{atomic::Ordering, Arc};
+
+    use crate::Cell;
+
+    #[test]
+    #[cfg_attr(feature = "single_thread", allow(clippy::arc_with_non_send_sync))]
+    fn owned_cell_ref_outlives_arc_handle() {
+        let cell = Arc::new(Cell::new(vec![1u32, 2]));
+        let cell_clone = Arc::clone(&cell);
+
+        let owned = cell.borrow_owned();
+        drop(cell);
+        assert_eq!(vec![1, 2], *owned);
+        assert_eq!(1, cell_clone.flag.load(Ordering::SeqCst));
+        assert!(cell_clone.try_borrow_mut().is_err());
+
+        drop(owned);
+        assert_eq!(0, cell_clone.flag.load(Ordering::SeqCst));
+        assert_eq!(1, Arc::strong_count(&cell_clone));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "single_thread", allow(clippy::arc_with_non_send_sync))]
+    fn arc_get_mut_returns_none_while_owned_cell_ref_exists() {
+        let mut cell = Arc::new(Cell::new(1u32));
+
+        let owned = cell.borrow_owned();
+        assert!(Arc::get_mut(&mut cell).is_none());
+
+        drop(owned);
+        let cell_mut = Arc::get_mut(&mut cell).expect("Expected `Arc` to be unique.");
+        *cell_mut.get_mut() += 1;
+        assert_eq!(2, *cell.borrow());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "single_thread", allow(clippy::arc_with_non_send_sync))]
+    fn owned_cell_ref_keeps_cell_alive() {
+        let owned = Arc::new(Cell::new(String::from("a"))).borrow_owned();
+
+        assert_eq!("a", owned.as_str());
+        assert_eq!(1, Arc::strong_count(&owned.cell));
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected to borrow `u32` immutably, but it was already borrowed")]
+    #[cfg_attr(feature = "single_thread", allow(clippy::arc_with_non_send_sync))]
+    fn borrow_owned_panics_when_mutably_borrowed() {
+        let cell = Arc::new(Cell::new(1u32));
+
+        let _a = cell.borrow_mut();
+        let _b = cell.borrow_owned();
+    }
+
+    #[cfg(not(feature = "single_thread"))]
+    #[test]
+    fn owned_cell_ref_can_be_sent_to_another_thread() {
+        let cell = Arc::new(Cell::new(5u32));
+        let owned = cell.borrow_owned();
+
+        let value = std::thread::spawn(move || *owned)
+            .join()
+            .expect("Expected thread to succeed.");
+
+        assert_eq!(5, value);
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+    }
+
+    #[cfg(not(feature = "unsafe_debug"))]
+    #[test]
+    #[cfg_attr(feature = "single_thread", allow(clippy::arc_with_non_send_sync))]
+    fn debug_includes_reader_count() {
+        let owned = Arc::new(Cell::new(1u32)).borrow_owned();
+
+        assert_eq!("OwnedCellRef { readers: 1 }", format!("{owned:?}"));
+    }
+}
@@ -0,0 +1,172 @@
+use std::{
+    fmt,
+    ops::{Deref, DerefMut},
+    sync::Arc,
+};
+
+use crate::{Cell, CellRefMut};
+
+/// A mutable reference to data in an `Arc<Cell<T>>`, which keeps the `Cell`
+/// alive.
+///
+/// Returned by [`Cell::borrow_mut_owned`]. Unlike a [`CellRefMut`], this does
+/// not borrow the `Cell`'s handle, so it may be held after the `Arc` it was
+/// borrowed from is dropped, or moved to another thread.
+///
+/// Access the value via `std::ops::DerefMut` (e.g. `*val`)
+///
+/// [`Cell::borrow_mut_owned`]: crate::Cell::borrow_mut_owned
+pub struct OwnedCellRefMut<T>
+where
+    T: ?Sized,
+{
+    /// Cell whose value is mutably borrowed by this guard.
+    pub(crate) cell: Arc<Cell<T>>,
+}
+
+impl<T> Deref for OwnedCellRefMut<T>
+where
+    T: ?Sized,
+{
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // This holds the mutable borrow, so the value is not aliased.
+        unsafe { &*self.cell.inner.get() }
+    }
+}
+
+impl<T> DerefMut for OwnedCellRefMut<T>
+where
+    T: ?Sized,
+{
+    fn deref_mut(&mut self) -> &mut T {
+        // This holds the mutable borrow, so the value is not aliased.
+        unsafe { &mut *self.cell.inner.get() }
+    }
+}
+
+#[cfg(not(feature = "unsafe_debug"))]
+impl<T> fmt::Debug for OwnedCellRefMut<T>
+where
+    T: ?Sized,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OwnedCellRefMut")
+            .field("flag", &self.cell.flag)
+            .field("value", &format_args!(".."))
+            .finish()
+    }
+}
+
+#[cfg(feature = "unsafe_debug")]
+impl<T> fmt::Debug for OwnedCellRefMut<T>
+where
+    T: ?Sized + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OwnedCellRefMut")
+            .field("flag", &self.cell.flag)
+            .field("value", &&**self)
+            .finish()
+    }
+}
+
+impl<T> Drop for OwnedCellRefMut<T>
+where
+    T: ?Sized,
+{
+    fn drop(&mut self) {
+        // Releases the mutable borrow the same way as a `CellRefMut`.
+        drop(CellRefMut {
+            flag: &self.cell.flag,
+            value: &mut (),
+            shared: false,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{atomic::Ordering, Arc};
+
+    use crate::{BorrowFail, Cell};
+
+    #[test]
+    #[cfg_attr(feature = "single_thread", allow(clippy::arc_with_non_send_sync))]
+    fn arc_get_mut_returns_none_while_owned_cell_ref_mut_exists() {
+        let mut cell = Arc::new(Cell::new(1u32));
+
+        let mut owned = cell.borrow_mut_owned();
+        *owned += 1;
+        assert!(Arc::get_mut(&mut cell).is_none());
+
+        drop(owned);
+        let cell_mut = Arc::get_mut(&mut cell).expect("Expected `Arc` to be unique.");
+        *cell_mut.get_mut() += 1;
+        assert_eq!(3, *cell.borrow());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "single_thread", allow(clippy::arc_with_non_send_sync))]
+    fn owned_cell_ref_mut_mutates_value_after_arc_handle_dropped() {
+        let cell = Arc::new(Cell::new(vec![1u32]));
+        let cell_clone = Arc::clone(&cell);
+
+        let mut owned = cell.borrow_mut_owned();
+        drop(cell);
+        owned.push(2);
+        assert_eq!(usize::MAX, cell_clone.flag.load(Ordering::SeqCst));
+
+        drop(owned);
+        assert_eq!(0, cell_clone.flag.load(Ordering::SeqCst));
+        assert_eq!(vec![1, 2], *cell_clone.borrow());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "single_thread", allow(clippy::arc_with_non_send_sync))]
+    fn owned_cell_ref_mut_excludes_other_borrows() {
+        let cell = Arc::new(Cell::new(1u32));
+
+        let owned = cell.borrow_mut_owned();
+        assert_eq!(
+            Err(BorrowFail::BorrowConflictImm),
+            cell.try_borrow().map(|_| ())
+        );
+        assert_eq!(
+            Err(BorrowFail::WriteLocked),
+            cell.try_borrow_mut().map(|_| ())
+        );
+
+        drop(owned);
+        let _r = cell.borrow();
+        assert_eq!(
+            Err(BorrowFail::ReadLocked(1)),
+            cell.try_borrow_mut().map(|_| ())
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected to borrow `u32` mutably, but it was already borrowed")]
+    #[cfg_attr(feature = "single_thread", allow(clippy::arc_with_non_send_sync))]
+    fn borrow_mut_owned_panics_when_borrowed() {
+        let cell = Arc::new(Cell::new(1u32));
+
+        let _a = cell.borrow();
+        let _b = cell.borrow_mut_owned();
+    }
+
+    #[cfg(not(feature = "single_thread"))]
+    #[test]
+    fn owned_cell_ref_mut_can_be_sent_to_another_thread() {
+        let cell = Arc::new(Cell::new(5u32));
+        let mut owned = cell.borrow_mut_owned();
+
+        std::thread::spawn(move || *owned += 1)
+            .join()
+            .expect("Expected thread to succeed.");
+
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+        assert_eq!(6, *cell.borrow());
+    }
+}
@@ -0,0 +1,25 @@
+//! Commonly imported types.
+//!
+//! ```rust
+//! use rt_ref::prelude::*;
+//!
+//! let cell = Cell::new(1);
+//!
+//! let mut a: RefMut<'_, u32> = RefMut::new(cell.borrow_mut());
+//! *a += 1;
+//! assert!(matches!(cell.try_borrow(), Err(BorrowFail::BorrowConflictImm)));
+//! drop(a);
+//!
+//! let b: Ref<'_, u32> = Ref::new(cell.borrow());
+//! let c: Result<Ref<'_, u32>, RefOverflow> = b.try_clone();
+//! assert_eq!(Ok(2), c.map(|c| *c));
+//!
+//! let d: CellRef<'_, u32> = cell.borrow();
+//! assert_eq!(2, *d);
+//! drop((b, d));
+//!
+//! let e: CellRefMut<'_, u32> = cell.borrow_mut();
+//! assert_eq!(2, *e);
+//! ```
+
+pub use crate::{BorrowFail, Cell, CellRef, CellRefMut, HasLen, Ref, RefMut, RefOverflow};
@@ -0,0 +1,33 @@
+use crate::flag::Flag;
+
+/// The borrow held by a `CellRef` or `CellRefMut`, separated from its value.
+///
+/// Returned by [`CellRef::into_raw_parts`] and [`CellRefMut::into_raw_parts`].
+/// The borrow is not released when this is dropped, so it must be passed to
+/// the matching `from_raw_parts` to release it.
+///
+/// [`CellRef::into_raw_parts`]: crate::CellRef::into_raw_parts
+/// [`CellRefMut::into_raw_parts`]: crate::CellRefMut::into_raw_parts
+#[derive(Debug)]
+pub struct RawBorrow<'a> {
+    pub(crate) flag: &'a Flag,
+    /// Whether a mutable borrow is shared with other `CellRefMut`s.
+    pub(crate) shared: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Cell;
+
+    #[test]
+    fn debug_includes_flag() {
+        let cell = Cell::new(1u32);
+
+        let (raw_borrow, _value) = cell.borrow().into_raw_parts();
+
+        assert_eq!(
+            "RawBorrow { flag: 1, shared: false }",
+            format!("{raw_borrow:?}")
+        );
+    }
+}
@@ -0,0 +1,148 @@
+use std::ops::Deref;
+
+use crate::flag::{release_reader, Flag};
+
+/// An immutable borrow of a `Cell` that is shared with nested references
+/// without updating the borrow flag.
+///
+/// Returned by [`Cell::borrow_reentrant`]. This is intended for recursive
+/// read-only traversals: instead of borrowing the `Cell` at each level, which
+/// increments the reader count once per level, each level calls [`reenter`]
+/// on its parent. All nested references share the outermost borrow, so the
+/// reader count stays at `1` regardless of depth, and is released when the
+/// outermost `ReentrantRef` is dropped.
+///
+/// # Examples
+///
+/// ```rust
+/// use rt_ref::{Cell, ReentrantRef};
+///
+/// fn depth(tree: ReentrantRef<'_, Vec<u32>>, level: usize) -> usize {
+///     if level == tree.len() {
+///         level
+///     } else {
+///         depth(tree.reenter(), level + 1)
+///     }
+/// }
+///
+/// let cell = Cell::new(vec![1, 2, 3]);
+///
+/// assert_eq!(3, depth(cell.borrow_reentrant(), 0));
+/// assert!(cell.try_borrow_mut().is_ok());
+/// ```
+///
+/// [`Cell::borrow_reentrant`]: crate::Cell::borrow_reentrant
+/// [`reenter`]: Self::reenter
+#[derive(Debug)]
+pub struct ReentrantRef<'a, T>
+where
+    T: ?Sized + 'a,
+{
+    pub(crate) value: &'a T,
+    /// Flag whose reader slot is held by the outermost `ReentrantRef`, and is
+    /// `None` for nested references.
+    pub(crate) flag: Option<&'a Flag>,
+}
+
+impl<'a, T> ReentrantRef<'a, T>
+where
+    T: ?Sized,
+{
+    /// Returns a nested reference that shares this borrow.
+    ///
+    /// This does not update the borrow flag. The nested reference cannot
+    /// outlive this `ReentrantRef`.
+    pub fn reenter(&self) -> ReentrantRef<'_, T> {
+        ReentrantRef {
+            value: self.value,
+            flag: None,
+        }
+    }
+}
+
+impl<'a, T> Drop for ReentrantRef<'a, T>
+where
+    T: ?Sized,
+{
+    fn drop(&mut self) {
+        if let Some(flag) = self.flag {
+            release_reader(flag);
+        }
+    }
+}
+
+impl<'a, T> Deref for ReentrantRef<'a, T>
+where
+    T: ?Sized,
+{
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::Ordering;
+
+    use crate::Cell;
+
+    use super::ReentrantRef;
+
+    #[test]
+    fn nested_references_share_one_borrow() {
+        fn visit(
+            cell: &Cell<Vec<u32>>,
+            r: ReentrantRef<'_, Vec<u32>>,
+            depth: usize,
+            sum: &mut u32,
+        ) {
+            assert_eq!(1, cell.flag.load(Ordering::SeqCst));
+            assert!(cell.try_borrow_mut().is_err());
+
+            *sum += r[depth % r.len()];
+            if depth < 1_000 {
+                visit(cell, r.reenter(), depth + 1, sum);
+            }
+
+            assert_eq!(1, cell.flag.load(Ordering::SeqCst));
+        }
+
+        let cell = Cell::new(vec![1u32, 2]);
+
+        let mut sum = 0;
+        visit(&cell, cell.borrow_reentrant(), 0, &mut sum);
+
+        assert_eq!(1_501, sum);
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn nested_reference_drop_does_not_release_borrow() {
+        let cell = Cell::new(1u32);
+
+        let r = cell.borrow_reentrant();
+        let nested = r.reenter();
+        let nested_nested = nested.reenter();
+        assert_eq!(1, *nested_nested);
+
+        drop(nested_nested);
+        drop(nested);
+        assert_eq!(1, cell.flag.load(Ordering::SeqCst));
+
+        drop(r);
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Expected to borrow `u32` immutably, but it was already borrowed mutably"
+    )]
+    fn borrow_reentrant_panics_when_borrowed_mutably() {
+        let cell = Cell::new(1u32);
+
+        let _r_mut = cell.borrow_mut();
+        let _r = cell.borrow_reentrant();
+    }
+}
@@ -1,16 +1,23 @@
-use std::{cmp::PartialEq, fmt, ops::Deref};
+use std::{
+    cmp::PartialEq,
+    fmt,
+    ops::{Deref, Index},
+};
 
-use crate::{CellRef, RefOverflow};
+use crate::{CellRef, RefMut, RefOverflow};
 
 /// Reference to a value.
 pub struct Ref<'a, V>
 where
-    V: 'a,
+    V: ?Sized + 'a,
 {
     pub(crate) inner: CellRef<'a, V>,
 }
 
-impl<'a, V> Ref<'a, V> {
+impl<'a, V> Ref<'a, V>
+where
+    V: ?Sized,
+{
     /// Returns a new `Ref`.
     pub fn new(inner: CellRef<'a, V>) -> Self {
         Self { inner }
@@ -27,14 +34,36 @@ impl<'a, V> Ref<'a, V> {
     /// * Applications that hold `Ref`s with an ever-increasing reference count
     ///   are not supported by this library.
     ///
-    ///     Reaching `isize::MAX` may be possible with
-    ///     `std::mem::forget(Ref::clone(&r))`.
+    ///   Reaching `isize::MAX` may be possible with
+    ///   `std::mem::forget(Ref::clone(&r))`.
     pub fn try_clone(&self) -> Result<Self, RefOverflow> {
         self.inner.try_clone().map(Self::new)
     }
+
+    /// Returns a value computed from the borrowed data, releasing the borrow.
+    ///
+    /// See [`CellRef::read`].
+    pub fn read<R, F>(self, f: F) -> R
+    where
+        F: FnOnce(&V) -> R,
+    {
+        self.inner.read(f)
+    }
 }
 
-impl<'a, V> Deref for Ref<'a, V> {
+impl<'a, V> From<CellRef<'a, V>> for Ref<'a, V>
+where
+    V: ?Sized,
+{
+    fn from(inner: CellRef<'a, V>) -> Self {
+        Self::new(inner)
+    }
+}
+
+impl<'a, V> Deref for Ref<'a, V>
+where
+    V: ?Sized,
+{
     type Target = V;
 
     fn deref(&self) -> &V {
@@ -42,19 +71,30 @@ impl<'a, V> Deref for Ref<'a, V> {
     }
 }
 
+impl<'a, V, I> Index<I> for Ref<'a, V>
+where
+    V: Index<I> + ?Sized,
+{
+    type Output = V::Output;
+
+    fn index(&self, index: I) -> &V::Output {
+        (**self).index(index)
+    }
+}
+
 impl<'a, V> fmt::Debug for Ref<'a, V>
 where
-    V: fmt::Debug + 'a,
+    V: fmt::Debug + ?Sized + 'a,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let inner: &V = self;
-        f.debug_struct("Ref").field("inner", inner).finish()
+        f.debug_struct("Ref").field("inner", &inner).finish()
     }
 }
 
 impl<'a, V> PartialEq for Ref<'a, V>
 where
-    V: PartialEq + 'a,
+    V: PartialEq + ?Sized + 'a,
 {
     fn eq(&self, other: &Self) -> bool {
         let r_self: &V = self;
@@ -63,20 +103,37 @@ where
     }
 }
 
-impl<'a, V> Clone for Ref<'a, V> {
+impl<'a, 'b, V> PartialEq<RefMut<'b, V>> for Ref<'a, V>
+where
+    V: PartialEq + ?Sized + 'a,
+{
+    fn eq(&self, other: &RefMut<'b, V>) -> bool {
+        let r_self: &V = self;
+        let r_other: &V = other;
+        r_self == r_other
+    }
+}
+
+impl<'a, V> Clone for Ref<'a, V>
+where
+    V: ?Sized,
+{
     /// Returns a clone of this `Ref`.
     ///
     /// # Panics
     ///
-    /// Panics if the number of references is `isize::MAX`:
+    /// Panics if the number of references has reached the flag limit, which
+    /// is `isize::MAX` unless set through [`CellBuilder`]:
     ///
     /// * Having 2 billion / 9 quintillion references to an object is not a
     ///   realistic scenario in most applications.
     /// * Applications that hold `Ref`s with an ever-increasing reference count
     ///   are not supported by this library.
     ///
-    ///     Reaching `isize::MAX` may be possible with
-    ///     `std::mem::forget(Ref::clone(&r))`.
+    ///   Reaching `isize::MAX` may be possible with
+    ///   `std::mem::forget(Ref::clone(&r))`.
+    ///
+    /// [`CellBuilder`]: crate::CellBuilder
     fn clone(&self) -> Self {
         Ref {
             inner: self.inner.clone(),
@@ -88,16 +145,19 @@ impl<'a, V> Clone for Ref<'a, V> {
 mod tests {
     use std::{
         fmt::{self, Write},
-        sync::atomic::{AtomicUsize, Ordering},
+        ops::Index,
+        sync::atomic::Ordering,
     };
 
-    use crate::{cell_ref::REF_LIMIT_MAX, CellRef, RefOverflow};
+    use crate::{
+        cell_ref::REF_LIMIT_MAX, flag::Flag, Cell, CellRef, CellRefMut, RefMut, RefOverflow,
+    };
 
     use super::Ref;
 
     #[test]
     fn debug_includes_inner_field() -> fmt::Result {
-        let flag = AtomicUsize::new(0);
+        let flag = Flag::new(1);
         let value = A(1);
         let r#ref = Ref::new(CellRef {
             flag: &flag,
@@ -113,7 +173,7 @@ mod tests {
 
     #[test]
     fn partial_eq_compares_value() -> fmt::Result {
-        let flag = AtomicUsize::new(0);
+        let flag = Flag::new(3);
         let value = A(1);
         let r#ref = Ref::new(CellRef {
             flag: &flag,
@@ -138,9 +198,36 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn partial_eq_ref_mut_compares_value() {
+        let flag = Flag::new(1);
+        let r#ref = Ref::new(CellRef {
+            flag: &flag,
+            value: &1,
+        });
+
+        let flag_other = Flag::new(usize::MAX);
+        assert_eq!(
+            r#ref,
+            RefMut::new(CellRefMut {
+                flag: &flag_other,
+                value: &mut 1,
+                shared: false,
+            })
+        );
+        assert_ne!(
+            r#ref,
+            RefMut::new(CellRefMut {
+                flag: &flag_other,
+                value: &mut 2,
+                shared: false,
+            })
+        );
+    }
+
     #[test]
     fn try_clone_returns_ok_when_ref_count_less_than_usize_max() {
-        let flag = &AtomicUsize::new(1);
+        let flag = &Flag::new(1);
         let value = &A(1);
         let ref_0 = Ref::new(CellRef { flag, value });
 
@@ -155,7 +242,7 @@ mod tests {
 
     #[test]
     fn try_clone_returns_err_when_ref_count_equals_usize_max() {
-        let flag = &AtomicUsize::new(REF_LIMIT_MAX);
+        let flag = &Flag::new(REF_LIMIT_MAX);
         let value = &A(1);
         let ref_0 = Ref::new(CellRef { flag, value });
 
@@ -164,7 +251,7 @@ mod tests {
         let try_clone_result = ref_0.try_clone();
 
         let e = try_clone_result.expect_err("try_clone_result to be err");
-        assert_eq!(RefOverflow, e);
+        assert_eq!(RefOverflow::new::<A>(REF_LIMIT_MAX), e);
 
         // Ensure that the overflow is not persisted
         assert_eq!(REF_LIMIT_MAX, ref_0.inner.flag.load(Ordering::SeqCst));
@@ -172,7 +259,7 @@ mod tests {
 
     #[test]
     fn clone_increments_cell_ref_count() {
-        let flag = &AtomicUsize::new(1);
+        let flag = &Flag::new(1);
         let value = &A(1);
         let ref_0 = Ref::new(CellRef { flag, value });
 
@@ -185,9 +272,19 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "Failed to clone `CellRef`: Ref count exceeded `isize::MAX`")]
+    #[cfg(not(feature = "abort_on_overflow"))]
+    #[cfg_attr(
+        not(feature = "track_borrows"),
+        should_panic(expected = "Failed to clone `CellRef`: Ref count reached the flag limit")
+    )]
+    #[cfg_attr(
+        feature = "track_borrows",
+        should_panic(
+            expected = "Failed to clone `CellRef`: Ref count for `rt_ref::ref::tests::A` reached the flag limit"
+        )
+    )]
     fn clone_panics_when_ref_count_equals_usize_max() {
-        let flag = &AtomicUsize::new(REF_LIMIT_MAX);
+        let flag = &Flag::new(REF_LIMIT_MAX);
         let value = &A(1);
         let ref_0 = Ref::new(CellRef { flag, value });
 
@@ -196,6 +293,40 @@ mod tests {
         let _cloned = ref_0.clone();
     }
 
+    #[test]
+    fn index_forwards_to_inner_value() {
+        fn first<C: Index<usize, Output = u32>>(c: &C) -> u32 {
+            c[0]
+        }
+
+        let cell = Cell::new(vec![1u32, 2, 3]);
+
+        let r#ref = Ref::new(cell.borrow());
+        assert_eq!(1, first(&r#ref));
+        assert_eq!(3, r#ref[2]);
+        assert_eq!(&[2, 3], &r#ref[1..]);
+    }
+
+    #[test]
+    fn read_returns_computed_value_and_releases_borrow() {
+        let cell = Cell::new(A(1));
+
+        let s = Ref::new(cell.borrow()).read(|a| format!("{a:?}"));
+
+        assert_eq!("A(1)", s);
+        assert_eq!(0, cell.flag_raw());
+    }
+
+    #[test]
+    fn from_cell_ref_preserves_borrow() {
+        let cell = Cell::new(A(1));
+
+        let r#ref: Ref<'_, A> = cell.borrow().into();
+
+        assert_eq!(&A(1), &*r#ref);
+        assert_eq!(1, r#ref.inner.flag.load(Ordering::SeqCst));
+    }
+
     #[derive(Debug, Clone, PartialEq)]
     struct A(usize);
 }
@@ -1,32 +1,34 @@
 use std::{
     cmp::PartialEq,
-    fmt,
-    ops::{Deref, DerefMut},
+    fmt, io,
+    ops::{Deref, DerefMut, Index, IndexMut},
 };
 
+use crate::Ref;
+
 pub use crate::cell_ref_mut::CellRefMut;
 
 /// Mutable reference to a value.
 pub struct RefMut<'a, V>
 where
-    V: 'a,
+    V: ?Sized + 'a,
 {
     pub(crate) inner: CellRefMut<'a, V>,
 }
 
 impl<'a, V> fmt::Debug for RefMut<'a, V>
 where
-    V: fmt::Debug + 'a,
+    V: fmt::Debug + ?Sized + 'a,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let inner: &V = self;
-        f.debug_struct("RefMut").field("inner", inner).finish()
+        f.debug_struct("RefMut").field("inner", &inner).finish()
     }
 }
 
 impl<'a, V> PartialEq for RefMut<'a, V>
 where
-    V: PartialEq + 'a,
+    V: PartialEq + ?Sized + 'a,
 {
     fn eq(&self, other: &Self) -> bool {
         let r_self: &V = self;
@@ -35,13 +37,56 @@ where
     }
 }
 
-impl<'a, V> RefMut<'a, V> {
+impl<'a, 'b, V> PartialEq<Ref<'b, V>> for RefMut<'a, V>
+where
+    V: PartialEq + ?Sized + 'a,
+{
+    fn eq(&self, other: &Ref<'b, V>) -> bool {
+        let r_self: &V = self;
+        let r_other: &V = other;
+        r_self == r_other
+    }
+}
+
+impl<'a, V> RefMut<'a, V>
+where
+    V: ?Sized,
+{
     pub fn new(inner: CellRefMut<'a, V>) -> Self {
         Self { inner }
     }
+
+    /// Splits the borrowed data into two disjoint components.
+    ///
+    /// Both returned `RefMut`s share the existing mutable borrow, which is
+    /// released when both are dropped.
+    ///
+    /// See [`CellRefMut::map_split`].
+    pub fn map_split<U, W, F>(self, f: F) -> (RefMut<'a, U>, RefMut<'a, W>)
+    where
+        F: FnOnce(&mut V) -> (&mut U, &mut W),
+        U: ?Sized,
+        W: ?Sized,
+    {
+        let (u, w) = self.inner.map_split(f);
+
+        (RefMut::new(u), RefMut::new(w))
+    }
 }
 
-impl<'a, V> Deref for RefMut<'a, V> {
+impl<'a, V> From<CellRefMut<'a, V>> for RefMut<'a, V>
+where
+    V: ?Sized,
+{
+    fn from(inner: CellRefMut<'a, V>) -> Self {
+        Self::new(inner)
+    }
+}
+
+impl<'a, V> Deref for RefMut<'a, V>
+where
+    V: ?Sized,
+{
     type Target = V;
 
     fn deref(&self) -> &V {
@@ -49,30 +94,225 @@ impl<'a, V> Deref for RefMut<'a, V> {
     }
 }
 
-impl<'a, V> DerefMut for RefMut<'a, V> {
+impl<'a, V> DerefMut for RefMut<'a, V>
+where
+    V: ?Sized,
+{
     fn deref_mut(&mut self) -> &mut V {
         &mut self.inner
     }
 }
 
+impl<'a, V, I> Index<I> for RefMut<'a, V>
+where
+    V: Index<I> + ?Sized,
+{
+    type Output = V::Output;
+
+    fn index(&self, index: I) -> &V::Output {
+        (**self).index(index)
+    }
+}
+
+impl<'a, V, I> IndexMut<I> for RefMut<'a, V>
+where
+    V: IndexMut<I> + ?Sized,
+{
+    fn index_mut(&mut self, index: I) -> &mut V::Output {
+        (**self).index_mut(index)
+    }
+}
+
+impl<'a, I> Iterator for RefMut<'a, I>
+where
+    I: Iterator,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        (**self).next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (**self).size_hint()
+    }
+}
+
+impl<'a, A, E> Extend<A> for RefMut<'a, E>
+where
+    E: Extend<A>,
+{
+    fn extend<T>(&mut self, iter: T)
+    where
+        T: IntoIterator<Item = A>,
+    {
+        (**self).extend(iter)
+    }
+}
+
+impl<'a, W> io::Write for RefMut<'a, W>
+where
+    W: io::Write + ?Sized,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        (**self).write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        (**self).flush()
+    }
+}
+
+impl<'a, R> io::Read for RefMut<'a, R>
+where
+    R: io::Read + ?Sized,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        (**self).read(buf)
+    }
+}
+
+impl<'a, W> fmt::Write for RefMut<'a, W>
+where
+    W: fmt::Write + ?Sized,
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        (**self).write_str(s)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::{
         fmt::{self, Write},
-        sync::atomic::AtomicUsize,
+        io,
+        ops::IndexMut,
+        sync::atomic::Ordering,
     };
 
-    use crate::CellRefMut;
+    use crate::{flag::Flag, Cell, CellRef, CellRefMut, Ref};
 
     use super::RefMut;
 
+    #[test]
+    fn map_split_mutates_both_fields() {
+        let cell = Cell::new((1u32, 'a'));
+
+        let (mut n, mut ch) = RefMut::new(cell.borrow_mut()).map_split(|(n, ch)| (n, ch));
+        *n += 1;
+        *ch = 'b';
+        assert!(cell.try_borrow().is_err());
+
+        drop((n, ch));
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+        assert_eq!((2, 'b'), *cell.borrow());
+    }
+
+    #[test]
+    fn index_mut_forwards_to_inner_value() {
+        fn increment_first<C: IndexMut<usize, Output = u32>>(c: &mut C) {
+            c[0] += 1;
+        }
+
+        let cell = Cell::new(vec![1u32, 2, 3]);
+
+        let mut ref_mut = RefMut::new(cell.borrow_mut());
+        increment_first(&mut ref_mut);
+        ref_mut[2] = 5;
+        assert_eq!(2, ref_mut[1]);
+
+        drop(ref_mut);
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+        assert_eq!(vec![2, 2, 5], *cell.borrow());
+    }
+
+    #[test]
+    fn extend_forwards_to_inner_value() {
+        fn extend_with_evens(e: &mut impl Extend<u32>) {
+            e.extend([2, 4]);
+        }
+
+        let cell = Cell::new(vec![1u32]);
+
+        let mut ref_mut = RefMut::new(cell.borrow_mut());
+        extend_with_evens(&mut ref_mut);
+        assert_eq!(vec![1, 2, 4], *ref_mut);
+
+        drop(ref_mut);
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+        assert_eq!(vec![1, 2, 4], *cell.borrow());
+    }
+
+    #[test]
+    fn io_write_forwards_to_inner_writer() -> io::Result<()> {
+        fn write_greeting(w: &mut impl io::Write) -> io::Result<()> {
+            w.write_all(b"hello")?;
+            write!(w, ", world {}", 1)?;
+            w.flush()
+        }
+
+        let cell = Cell::new(Vec::<u8>::new());
+
+        let mut ref_mut = RefMut::new(cell.borrow_mut());
+        write_greeting(&mut ref_mut)?;
+        assert_eq!(b"hello, world 1", ref_mut.as_slice());
+
+        drop(ref_mut);
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+        assert_eq!(b"hello, world 1", cell.borrow().as_slice());
+
+        Ok(())
+    }
+
+    #[test]
+    fn io_read_forwards_to_inner_reader() -> io::Result<()> {
+        fn read_to_string(r: &mut impl io::Read) -> io::Result<String> {
+            let mut s = String::new();
+            r.read_to_string(&mut s)?;
+            Ok(s)
+        }
+
+        let cell = Cell::new(io::Cursor::new(b"hello".to_vec()));
+
+        let mut ref_mut = RefMut::new(cell.borrow_mut());
+        assert_eq!("hello", read_to_string(&mut ref_mut)?);
+        assert_eq!(5, ref_mut.position());
+
+        drop(ref_mut);
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+        assert_eq!("", read_to_string(&mut RefMut::new(cell.borrow_mut()))?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn fmt_write_forwards_to_inner_value() -> fmt::Result {
+        fn write_greeting(w: &mut impl Write, name: &str) -> fmt::Result {
+            write!(w, "hello, {name}")
+        }
+
+        let cell = Cell::new(String::new());
+
+        let mut ref_mut = RefMut::new(cell.borrow_mut());
+        write_greeting(&mut ref_mut, "world")?;
+        writeln!(ref_mut, "!")?;
+        assert_eq!("hello, world!\n", *ref_mut);
+
+        drop(ref_mut);
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+        assert_eq!("hello, world!\n", *cell.borrow());
+
+        Ok(())
+    }
+
     #[test]
     fn debug_includes_inner_field() -> fmt::Result {
-        let flag = AtomicUsize::new(0);
+        let flag = Flag::new(0);
         let mut value = A(1);
         let ref_mut = RefMut::new(CellRefMut {
             flag: &flag,
             value: &mut value,
+            shared: false,
         });
 
         let mut debug_string = String::with_capacity(64);
@@ -84,18 +324,20 @@ mod tests {
 
     #[test]
     fn partial_eq_compares_value() -> fmt::Result {
-        let flag = AtomicUsize::new(0);
+        let flag = Flag::new(0);
         let mut value = A(1);
         let mut value_clone = value.clone();
         let ref_mut = RefMut::new(CellRefMut {
             flag: &flag,
             value: &mut value,
+            shared: false,
         });
 
         assert_eq!(
             RefMut::new(CellRefMut {
                 flag: &flag,
                 value: &mut value_clone,
+                shared: false,
             }),
             ref_mut
         );
@@ -103,6 +345,7 @@ mod tests {
             RefMut::new(CellRefMut {
                 flag: &flag,
                 value: &mut A(2),
+                shared: false,
             }),
             ref_mut
         );
@@ -110,19 +353,48 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn partial_eq_ref_compares_value() {
+        let flag = Flag::new(0);
+        let mut value = A(1);
+        let ref_mut = RefMut::new(CellRefMut {
+            flag: &flag,
+            value: &mut value,
+            shared: false,
+        });
+
+        let flag_other = Flag::new(2);
+        assert_eq!(
+            ref_mut,
+            Ref::new(CellRef {
+                flag: &flag_other,
+                value: &A(1),
+            })
+        );
+        assert_ne!(
+            ref_mut,
+            Ref::new(CellRef {
+                flag: &flag_other,
+                value: &A(2),
+            })
+        );
+    }
+
     #[test]
     fn deref_mut_returns_value() -> fmt::Result {
-        let flag = AtomicUsize::new(0);
+        let flag = Flag::new(0);
         let mut value = A(1);
         let mut ref_mut = RefMut::new(CellRefMut {
             flag: &flag,
             value: &mut value,
+            shared: false,
         });
 
         assert_eq!(
             RefMut::new(CellRefMut {
                 flag: &flag,
                 value: &mut A(1),
+                shared: false,
             }),
             ref_mut
         );
@@ -133,6 +405,7 @@ mod tests {
             RefMut::new(CellRefMut {
                 flag: &flag,
                 value: &mut A(2),
+                shared: false,
             }),
             ref_mut
         );
@@ -140,6 +413,31 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn from_cell_ref_mut_preserves_borrow() {
+        let cell = Cell::new(A(1));
+
+        let mut ref_mut: RefMut<'_, A> = cell.borrow_mut().into();
+        ref_mut.0 = 2;
+
+        assert_eq!(&A(2), &*ref_mut);
+        assert_eq!(usize::MAX, ref_mut.inner.flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn iterator_forwards_to_inner() {
+        let cell = Cell::new(vec![1, 2, 3].into_iter());
+
+        let ref_mut = RefMut::new(cell.borrow_mut());
+        assert_eq!((3, Some(3)), ref_mut.size_hint());
+
+        let doubled = ref_mut.map(|n| n * 2).collect::<Vec<u32>>();
+
+        assert_eq!(vec![2, 4, 6], doubled);
+        assert_eq!(0, cell.flag_raw());
+        assert_eq!(0, cell.borrow().len());
+    }
+
     #[derive(Debug, Clone, PartialEq)]
     struct A(usize);
 }
@@ -1,15 +1,65 @@
 use std::fmt;
 
-/// Error when trying to clone a [`Ref`], but there are already [`isize::MAX`]
-/// references.
+/// Error when trying to clone a [`Ref`], but the number of references has
+/// reached the `Cell`'s flag limit.
+///
+/// The limit is [`isize::MAX`] unless set through
+/// [`CellBuilder::flag_limit`]. With the `"track_borrows"` feature, this also
+/// holds the name of the borrowed type.
+///
+/// [`CellBuilder::flag_limit`]: crate::CellBuilder::flag_limit
 ///
 /// [`Ref`]: crate::Ref
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub struct RefOverflow;
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RefOverflow {
+    /// Name of the borrowed type, with the `"track_borrows"` feature.
+    type_name: Option<&'static str>,
+    /// Maximum number of references that was reached.
+    limit: usize,
+}
+
+impl RefOverflow {
+    /// Returns a `RefOverflow` for a borrow of `T` that reached `limit`.
+    #[cfg_attr(
+        not(feature = "track_borrows"),
+        allow(clippy::extra_unused_type_parameters)
+    )]
+    pub(crate) fn new<T>(limit: usize) -> Self
+    where
+        T: ?Sized,
+    {
+        #[cfg(not(feature = "track_borrows"))]
+        let type_name = None;
+
+        #[cfg(feature = "track_borrows")]
+        let type_name = Some(std::any::type_name::<T>());
+
+        RefOverflow { type_name, limit }
+    }
+
+    /// Returns the name of the borrowed type.
+    ///
+    /// This is only `Some` with the `"track_borrows"` feature.
+    pub fn type_name(&self) -> Option<&'static str> {
+        self.type_name
+    }
+
+    /// Returns the maximum number of references that was reached.
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+}
 
 impl fmt::Display for RefOverflow {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Ref count exceeded `isize::MAX` ({}).", isize::MAX)
+        match self.type_name {
+            Some(type_name) => write!(
+                f,
+                "Ref count for `{type_name}` reached the flag limit ({}).",
+                self.limit
+            ),
+            None => write!(f, "Ref count reached the flag limit ({}).", self.limit),
+        }
     }
 }
 
@@ -18,3 +68,56 @@ impl std::error::Error for RefOverflow {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "track_borrows")]
+    use crate::CellBuilder;
+
+    use super::RefOverflow;
+
+    #[cfg(not(feature = "track_borrows"))]
+    #[test]
+    fn display_includes_limit() {
+        let ref_overflow = RefOverflow::new::<u32>(3);
+
+        assert_eq!(None, ref_overflow.type_name());
+        assert_eq!(3, ref_overflow.limit());
+        assert_eq!(
+            "Ref count reached the flag limit (3).",
+            ref_overflow.to_string()
+        );
+    }
+
+    #[cfg(feature = "track_borrows")]
+    #[test]
+    fn display_includes_type_name_and_limit() {
+        struct MyResource;
+
+        let ref_overflow = RefOverflow::new::<MyResource>(3);
+
+        assert_eq!(
+            Some("rt_ref::ref_overflow::tests::display_includes_type_name_and_limit::MyResource"),
+            ref_overflow.type_name()
+        );
+        assert_eq!(3, ref_overflow.limit());
+        assert_eq!(
+            "Ref count for `rt_ref::ref_overflow::tests::display_includes_type_name_and_limit::MyResource` reached the flag limit (3).",
+            ref_overflow.to_string()
+        );
+    }
+
+    #[cfg(feature = "track_borrows")]
+    #[test]
+    fn try_clone_error_includes_type_name() {
+        let cell = CellBuilder::new(1u32).flag_limit(1).build();
+        let r = cell.borrow();
+
+        let e = r
+            .try_clone()
+            .map(|_| ())
+            .expect_err("Expected try_clone to fail.");
+        assert_eq!(Some("u32"), e.type_name());
+        assert_eq!(1, e.limit());
+    }
+}
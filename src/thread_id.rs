@@ -0,0 +1,14 @@
+//! Identifies threads without requiring `ThreadId::as_u64`, which is
+//! unstable.
+
+thread_local! {
+    static THREAD_MARKER: u8 = const { 0 };
+}
+
+/// Returns a non-zero ID for the current thread.
+///
+/// This is the address of a thread local, so it is unique among running
+/// threads, but may be reused after a thread exits.
+pub(crate) fn current() -> usize {
+    THREAD_MARKER.with(|marker| marker as *const u8 as usize)
+}
@@ -0,0 +1,90 @@
+use std::{
+    ops::{Deref, DerefMut},
+    sync::atomic::{AtomicU64, Ordering},
+    time::Instant,
+};
+
+use crate::CellRefMut;
+
+/// A mutable reference to data in a `Cell`, which records how long the borrow
+/// was held.
+///
+/// Returned by [`Cell::borrow_mut_timed`]. When this is dropped, the `Cell`'s
+/// [`max_borrow_mut_duration`] is raised to the duration of this borrow, if it
+/// is longer.
+///
+/// Access the value via `std::ops::DerefMut` (e.g. `*val`)
+///
+/// [`Cell::borrow_mut_timed`]: crate::Cell::borrow_mut_timed
+/// [`max_borrow_mut_duration`]: crate::Cell::max_borrow_mut_duration
+pub struct TimedRefMut<'a, T>
+where
+    T: ?Sized,
+{
+    pub(crate) cell_ref_mut: CellRefMut<'a, T>,
+    /// Longest duration of a timed mutable borrow of the `Cell`, in
+    /// nanoseconds.
+    pub(crate) max_nanos: &'a AtomicU64,
+    /// When the borrow was acquired.
+    pub(crate) start: Instant,
+}
+
+impl<'a, T> Deref for TimedRefMut<'a, T>
+where
+    T: ?Sized,
+{
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.cell_ref_mut
+    }
+}
+
+impl<'a, T> DerefMut for TimedRefMut<'a, T>
+where
+    T: ?Sized,
+{
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.cell_ref_mut
+    }
+}
+
+impl<'a, T> Drop for TimedRefMut<'a, T>
+where
+    T: ?Sized,
+{
+    fn drop(&mut self) {
+        // `cell_ref_mut` is dropped after this, releasing the borrow.
+        let nanos = u64::try_from(self.start.elapsed().as_nanos()).unwrap_or(u64::MAX);
+        self.max_nanos.fetch_max(nanos, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::atomic::Ordering, thread, time::Duration};
+
+    use crate::Cell;
+
+    #[test]
+    fn drop_records_borrow_duration() {
+        let cell = Cell::new(1u32);
+        assert_eq!(Duration::ZERO, cell.max_borrow_mut_duration());
+
+        let mut timed = cell.borrow_mut_timed();
+        *timed += 1;
+        thread::sleep(Duration::from_millis(2));
+        assert!(cell.try_borrow().is_err());
+
+        drop(timed);
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+        assert_eq!(2, *cell.borrow());
+
+        let max_borrow_mut_duration = cell.max_borrow_mut_duration();
+        assert!(max_borrow_mut_duration >= Duration::from_millis(2));
+
+        // A shorter borrow does not lower the maximum.
+        drop(cell.borrow_mut_timed());
+        assert_eq!(max_borrow_mut_duration, cell.max_borrow_mut_duration());
+    }
+}
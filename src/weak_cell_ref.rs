@@ -0,0 +1,102 @@
+use crate::{BorrowFail, Cell, CellRef};
+
+/// A handle to a `Cell` that does not borrow its value.
+///
+/// Unlike a [`CellRef`], this does not hold a reader slot, so the `Cell` may be
+/// borrowed mutably while this is held. Call [`upgrade`] to borrow the value.
+///
+/// [`upgrade`]: Self::upgrade
+#[derive(Debug)]
+pub struct WeakCellRef<'a, T> {
+    pub(crate) cell: &'a Cell<T>,
+}
+
+impl<'a, T> WeakCellRef<'a, T> {
+    /// Returns a new `WeakCellRef` to the given `Cell`.
+    pub fn new(cell: &'a Cell<T>) -> Self {
+        Self { cell }
+    }
+
+    /// Borrows the value immutably.
+    ///
+    /// Returns `BorrowConflictImm` if the value is borrowed mutably.
+    pub fn upgrade(&self) -> Result<CellRef<'a, T>, BorrowFail> {
+        self.cell.try_borrow()
+    }
+}
+
+impl<'a, T> Clone for WeakCellRef<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> Copy for WeakCellRef<'a, T> {}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::Ordering;
+
+    use crate::{BorrowFail, Cell};
+
+    use super::WeakCellRef;
+
+    #[test]
+    fn weak_cell_ref_does_not_hold_reader_slot() {
+        let cell = Cell::new(1u32);
+
+        let weak = WeakCellRef::new(&cell);
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+
+        *cell.borrow_mut() += 1;
+
+        assert_eq!(2, *weak.upgrade().expect("Expected upgrade to succeed."));
+    }
+
+    #[test]
+    fn downgrade_to_weak_releases_borrow() {
+        let cell = Cell::new(1u32);
+
+        let weak = cell.borrow().downgrade_to_weak(&cell);
+        assert_eq!(0, cell.flag.load(Ordering::SeqCst));
+        assert!(cell.try_borrow_mut().is_ok());
+
+        let cell_ref = weak.upgrade().expect("Expected upgrade to succeed.");
+        assert_eq!(1, *cell_ref);
+        assert_eq!(1, cell.flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn downgrade_to_weak_from_mapped_cell_ref_returns_weak_to_cell() {
+        let cell = Cell::new((1u32, 2u32));
+
+        let weak = cell.borrow().map(|pair| &pair.1).downgrade_to_weak(&cell);
+
+        assert_eq!(
+            (1, 2),
+            *weak.upgrade().expect("Expected upgrade to succeed.")
+        );
+    }
+
+    #[test]
+    fn upgrade_fails_while_borrowed_mutably() {
+        let cell = Cell::new(1u32);
+
+        let weak = WeakCellRef::new(&cell);
+        let _cell_ref_mut = cell.borrow_mut();
+
+        assert_eq!(
+            Err(BorrowFail::BorrowConflictImm),
+            weak.upgrade().map(|_| ())
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected `CellRef` to borrow from the given `Cell`.")]
+    fn downgrade_to_weak_panics_for_other_cell() {
+        let cell = Cell::new(1u32);
+        let cell_other = Cell::new(1u32);
+
+        let _weak = cell.borrow().downgrade_to_weak(&cell_other);
+    }
+}
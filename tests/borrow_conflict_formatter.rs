@@ -0,0 +1,30 @@
+//! Asserts that the borrow conflict formatter is used for panic messages.
+//!
+//! The formatter is process wide, so this is in its own test binary, to not
+//! change the panic messages expected by other tests.
+
+#![cfg(feature = "borrow_conflict_formatter")]
+
+use std::panic::{self, AssertUnwindSafe};
+
+use rt_ref::{set_borrow_conflict_formatter, Cell};
+
+#[test]
+fn borrow_panic_message_uses_formatter() {
+    set_borrow_conflict_formatter(|type_name, message| {
+        format!("{message} [resource: {type_name}]")
+    });
+
+    let cell = Cell::new(1u32);
+    let _a = cell.borrow_mut();
+    let payload = panic::catch_unwind(AssertUnwindSafe(|| {
+        let _b = cell.borrow();
+    }))
+    .expect_err("Expected borrow to panic.");
+
+    let message = payload
+        .downcast_ref::<String>()
+        .expect("Expected panic message to be a `String`.");
+    assert!(message.starts_with("Expected to borrow `u32` immutably, but it was already borrowed"));
+    assert!(message.ends_with(" [resource: u32]"), "{message}");
+}
@@ -0,0 +1,118 @@
+#![cfg(not(feature = "single_thread"))]
+
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+};
+
+use rt_ref::{BorrowFail, Cell};
+
+const THREAD_COUNT: usize = 8;
+const ITERATIONS: usize = 1_000;
+
+#[test]
+fn arc_cell_is_send_and_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    assert_send_sync::<Cell<u32>>();
+    assert_send_sync::<Arc<Cell<Vec<u32>>>>();
+}
+
+#[test]
+fn concurrent_borrows_are_mutually_exclusive() {
+    let cell = Arc::new(Cell::new(0usize));
+    let readers = Arc::new(AtomicUsize::new(0));
+    let writers = Arc::new(AtomicUsize::new(0));
+    let writes = Arc::new(AtomicUsize::new(0));
+
+    let handles = (0..THREAD_COUNT)
+        .map(|n| {
+            let cell = Arc::clone(&cell);
+            let readers = Arc::clone(&readers);
+            let writers = Arc::clone(&writers);
+            let writes = Arc::clone(&writes);
+
+            thread::spawn(move || {
+                for i in 0..ITERATIONS {
+                    if (n + i) % 2 == 0 {
+                        match cell.try_borrow_mut() {
+                            Ok(mut value) => {
+                                assert_eq!(0, writers.fetch_add(1, Ordering::SeqCst));
+                                assert_eq!(0, readers.load(Ordering::SeqCst));
+
+                                *value += 1;
+                                writes.fetch_add(1, Ordering::SeqCst);
+
+                                writers.fetch_sub(1, Ordering::SeqCst);
+                            }
+                            Err(e) => assert!(
+                                matches!(e, BorrowFail::ReadLocked(_) | BorrowFail::WriteLocked),
+                                "Unexpected failure: {e:?}"
+                            ),
+                        }
+                    } else {
+                        match cell.try_borrow() {
+                            Ok(value) => {
+                                readers.fetch_add(1, Ordering::SeqCst);
+                                assert_eq!(0, writers.load(Ordering::SeqCst));
+
+                                let _value = *value;
+
+                                readers.fetch_sub(1, Ordering::SeqCst);
+                            }
+                            Err(e) => assert_eq!(BorrowFail::BorrowConflictImm, e),
+                        }
+                    }
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    handles
+        .into_iter()
+        .for_each(|handle| handle.join().expect("Expected thread to not panic."));
+
+    assert_eq!(writes.load(Ordering::SeqCst), *cell.borrow());
+    assert!(cell.try_borrow_mut().is_ok());
+}
+
+#[test]
+fn concurrent_writes_are_not_lost() {
+    let cell = Arc::new(Cell::new(Vec::<usize>::new()));
+
+    let handles = (0..THREAD_COUNT)
+        .map(|n| {
+            let cell = Arc::clone(&cell);
+
+            thread::spawn(move || {
+                let mut i = 0;
+                while i < ITERATIONS {
+                    if let Ok(mut values) = cell.try_borrow_mut() {
+                        values.push(n);
+                        i += 1;
+                    } else {
+                        thread::yield_now();
+                    }
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    handles
+        .into_iter()
+        .for_each(|handle| handle.join().expect("Expected thread to not panic."));
+
+    let values = Arc::try_unwrap(cell)
+        .expect("Expected all threads to have dropped their `Arc`.")
+        .into_inner();
+    assert_eq!(THREAD_COUNT * ITERATIONS, values.len());
+    (0..THREAD_COUNT).for_each(|n| {
+        assert_eq!(
+            ITERATIONS,
+            values.iter().filter(|value| **value == n).count()
+        );
+    });
+}
@@ -0,0 +1,135 @@
+//! Asserts that each mutator releases the borrow when user code panics.
+//!
+//! Each case panics in a user supplied closure or in drop glue, then checks
+//! that the borrow flag is reset to `0` and the cell can be borrowed again.
+
+use std::panic::{self, AssertUnwindSafe};
+
+use rt_ref::{Cell, CellRefMut, RefMut};
+
+fn assert_released_after_panic<T, F>(cell: &Cell<T>, mutate: F)
+where
+    F: FnOnce(&Cell<T>),
+{
+    let result = panic::catch_unwind(AssertUnwindSafe(|| mutate(cell)));
+
+    assert!(result.is_err(), "Expected the mutator to panic.");
+    assert_eq!(0, cell.flag_raw());
+    assert!(cell.try_borrow_mut().is_ok());
+}
+
+#[test]
+fn borrow_map_releases_borrow_when_closure_panics() {
+    assert_released_after_panic(&Cell::new((1u32, 'a')), |cell| {
+        cell.borrow_map::<u32, _>(|_| panic!("Expected panic."));
+    });
+}
+
+#[test]
+fn borrow_mut_map_releases_borrow_when_closure_panics() {
+    assert_released_after_panic(&Cell::new((1u32, 'a')), |cell| {
+        cell.borrow_mut_map::<u32, _>(|_| panic!("Expected panic."));
+    });
+}
+
+#[test]
+fn borrow_mut_or_insert_with_releases_borrow_when_closure_panics() {
+    assert_released_after_panic(&Cell::new(None::<u32>), |cell| {
+        cell.borrow_mut_or_insert_with(|| panic!("Expected panic."));
+    });
+}
+
+#[test]
+fn borrow_mut_with_finalizer_releases_borrow_when_finalizer_panics() {
+    assert_released_after_panic(&Cell::new(1u32), |cell| {
+        let mut value = cell.borrow_mut_with_finalizer(|_| panic!("Expected panic."));
+        *value += 1;
+    });
+}
+
+#[test]
+fn extend_from_releases_borrow_when_iterator_panics() {
+    let cell = Cell::new(vec![1u32]);
+
+    assert_released_after_panic(&cell, |cell| {
+        cell.extend_from([2, 3, 4].into_iter().map(|n| {
+            if n < 4 {
+                n
+            } else {
+                panic!("Expected panic.")
+            }
+        }));
+    });
+    assert_eq!(vec![1, 2, 3], *cell.borrow());
+}
+
+#[test]
+fn compare_and_set_releases_borrow_when_partial_eq_panics() {
+    #[derive(Clone, Copy)]
+    struct PanicEq;
+
+    impl PartialEq for PanicEq {
+        fn eq(&self, _other: &Self) -> bool {
+            panic!("Expected panic.")
+        }
+    }
+
+    assert_released_after_panic(&Cell::new(PanicEq), |cell| {
+        let _result = cell.compare_and_set(PanicEq, PanicEq);
+    });
+}
+
+#[test]
+fn try_replace_releases_borrow_when_previous_value_drop_panics() {
+    struct PanicDrop(bool);
+
+    impl Drop for PanicDrop {
+        fn drop(&mut self) {
+            if self.0 {
+                panic!("Expected panic.");
+            }
+        }
+    }
+
+    assert_released_after_panic(&Cell::new(PanicDrop(true)), |cell| {
+        drop(cell.try_replace(PanicDrop(false)));
+    });
+}
+
+#[test]
+fn cell_ref_mut_map_releases_borrow_when_closure_panics() {
+    assert_released_after_panic(&Cell::new((1u32, 'a')), |cell| {
+        CellRefMut::map::<u32, _>(cell.borrow_mut(), |_| panic!("Expected panic."));
+    });
+}
+
+#[test]
+fn cell_ref_mut_and_then_releases_borrow_when_closure_panics() {
+    assert_released_after_panic(&Cell::new(vec![1u32]), |cell| {
+        cell.borrow_mut()
+            .and_then::<u32, _>(|_| panic!("Expected panic."));
+    });
+}
+
+#[test]
+fn cell_ref_mut_map_split_releases_borrow_when_closure_panics() {
+    assert_released_after_panic(&Cell::new((1u32, 'a')), |cell| {
+        cell.borrow_mut()
+            .map_split::<u32, char, _>(|_| panic!("Expected panic."));
+    });
+}
+
+#[test]
+fn cell_ref_mut_split_array_mut_releases_borrow_when_closure_panics() {
+    assert_released_after_panic(&Cell::new([1u32, 2]), |cell| {
+        cell.borrow_mut()
+            .split_array_mut::<u32, _, 2>(|_| panic!("Expected panic."));
+    });
+}
+
+#[test]
+fn ref_mut_map_split_releases_borrow_when_closure_panics() {
+    assert_released_after_panic(&Cell::new((1u32, 'a')), |cell| {
+        RefMut::new(cell.borrow_mut()).map_split::<u32, char, _>(|_| panic!("Expected panic."));
+    });
+}
@@ -0,0 +1,14 @@
+use std::sync::Arc;
+
+use rt_ref::Cell;
+
+fn main() {
+    let mut cell = Arc::new(Cell::new(1));
+
+    let cell_ref = cell.borrow();
+    if let Some(cell) = Arc::get_mut(&mut cell) {
+        *cell.get_mut() += 1;
+    }
+
+    let _value = *cell_ref;
+}
@@ -0,0 +1,11 @@
+use std::panic;
+
+use rt_ref::Cell;
+
+fn main() {
+    let cell = Cell::new(1u32);
+
+    let _result = panic::catch_unwind(|| {
+        *cell.borrow_mut() += 1;
+    });
+}
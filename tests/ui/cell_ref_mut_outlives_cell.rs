@@ -0,0 +1,11 @@
+use rt_ref::{Cell, CellRefMut};
+
+fn main() {
+    let cell_ref_mut: CellRefMut<'_, u32>;
+    {
+        let cell = Cell::new(1);
+        cell_ref_mut = cell.borrow_mut();
+    }
+
+    let _value = *cell_ref_mut;
+}
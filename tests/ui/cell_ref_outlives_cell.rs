@@ -0,0 +1,10 @@
+use rt_ref::{Cell, CellRef};
+
+fn cell_ref() -> CellRef<'static, u32> {
+    let cell = Cell::new(1);
+    cell.borrow()
+}
+
+fn main() {
+    let _cell_ref = cell_ref();
+}
@@ -0,0 +1,10 @@
+use rt_ref::Cell;
+
+fn main() {
+    let mut cell = Cell::new(1);
+
+    let cell_ref = cell.borrow();
+    *cell.get_mut() += 1;
+
+    let _value = *cell_ref;
+}
@@ -0,0 +1,10 @@
+use rt_ref::Cell;
+
+fn main() {
+    let cell = Cell::new(1);
+
+    let cell_ref_mut = cell.borrow_mut();
+    let _value = cell.into_inner();
+
+    drop(cell_ref_mut);
+}